@@ -0,0 +1,243 @@
+use std::path::{Path, PathBuf};
+
+use hyperborealib::rest_api::types::ServerAddress;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub storage: StorageConfig,
+    pub bootstrap: BootstrapConfig,
+    pub inbox: InboxConfig,
+    pub router: RouterConfig
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    /// Local address on which the HTTP server will listen.
+    pub local_address: String,
+
+    /// Address by which other network members
+    /// can reach this server through the Internet.
+    pub exposed_address: ServerAddress,
+
+    /// Time in seconds during which incoming
+    /// requests are considered valid.
+    pub request_lifetime: u64,
+
+    /// Maximal number of processed requests
+    /// remembered to reject their duplicates.
+    pub replay_guard_capacity: u64,
+
+    /// Minimal standard version of accepted requests.
+    pub min_standard: u64,
+
+    /// Forward messages sent to remote clients
+    /// to their servers from the routing table.
+    pub forward_messages: bool,
+
+    /// Verify announced servers by requesting
+    /// their info from the announced addresses.
+    pub probe_announced_servers: bool,
+
+    /// Maximal time in seconds for which poll
+    /// requests can wait for incoming messages.
+    pub max_poll_wait: u64,
+
+    /// Maximal time in seconds for which
+    /// polled messages can be leased.
+    pub max_lease: u64,
+
+    /// Maximal number of messages
+    /// in a single batch request.
+    pub max_batch_size: u64,
+
+    /// Maximal number of servers in a chain requested
+    /// by this server to resolve recursive lookups.
+    pub lookup_max_hops: u64,
+
+    /// Maximal time in seconds spent
+    /// on a single recursive lookup.
+    pub lookup_timeout: u64,
+
+    /// Serve HTTPS instead of plain HTTP.
+    pub tls: Option<TlsConfig>,
+
+    /// PEM encoded CA certificate trusted by the server
+    /// in addition to the system ones when it performs
+    /// requests to other network members.
+    pub ca_certificate: Option<PathBuf>,
+
+    /// Path to the Unix domain socket on which the HTTP
+    /// server will listen in addition to the local address.
+    pub unix_socket: Option<PathBuf>,
+
+    /// Octal permissions of the Unix domain socket file.
+    pub unix_socket_mode: String
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TlsConfig {
+    /// PEM encoded certificate chain of the server.
+    pub certificate: PathBuf,
+
+    /// PEM encoded private key of the certificate.
+    pub private_key: PathBuf
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// Folder where the server's secret key,
+    /// routing table and inbox are stored.
+    pub folder: PathBuf
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct BootstrapConfig {
+    /// Addresses of the servers used to gather
+    /// initial information about the network.
+    pub addresses: Vec<ServerAddress>,
+
+    /// Delay in seconds between network traversals.
+    pub traversal_delay: u64,
+
+    /// Index clients of the servers visited
+    /// during the network traversal.
+    pub harvest_clients: bool
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct InboxConfig {
+    /// Time in seconds after which unread
+    /// messages will be removed from the inbox.
+    pub lifetime: u64,
+
+    /// Maximal amount of messages stored for one receiver.
+    pub max_messages: Option<u64>,
+
+    /// Maximal total size in bytes of messages
+    /// stored for one receiver.
+    pub max_total_size: Option<u64>,
+
+    /// Maximal size in bytes of a single message.
+    pub max_message_size: Option<u64>,
+
+    /// Delay in seconds between removals
+    /// of outdated messages from the disk.
+    pub compaction_delay: u64
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct RouterConfig {
+    /// Time in seconds after which local clients
+    /// which sent no requests are reported unavailable.
+    pub local_timeout: u64,
+
+    /// Time in seconds after which announced
+    /// remote clients are reported unavailable.
+    pub remote_timeout: u64,
+
+    /// Time in seconds after which idle local clients
+    /// are removed from the routing table.
+    pub local_eviction: Option<u64>,
+
+    /// Time in seconds after which announced remote
+    /// clients are removed from the routing table.
+    pub remote_eviction: Option<u64>,
+
+    /// Delay in seconds between removals
+    /// of idle clients from the routing table.
+    pub sweep_delay: u64
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            local_address: String::from("0.0.0.0:8001"),
+            exposed_address: ServerAddress::new("http", "127.0.0.1", Some(8001), ""),
+            request_lifetime: 60 * 5,
+            replay_guard_capacity: 256 * 1024,
+            min_standard: 2,
+            forward_messages: false,
+            probe_announced_servers: true,
+            max_poll_wait: 30,
+            max_lease: 60 * 10,
+            max_batch_size: 128,
+            lookup_max_hops: 0,
+            lookup_timeout: 10,
+            tls: None,
+            ca_certificate: None,
+            unix_socket: None,
+            unix_socket_mode: String::from("660")
+        }
+    }
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            folder: PathBuf::from("storage")
+        }
+    }
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        Self {
+            addresses: Vec::new(),
+            traversal_delay: 60 * 10,
+            harvest_clients: true
+        }
+    }
+}
+
+impl Default for InboxConfig {
+    fn default() -> Self {
+        Self {
+            lifetime: 60 * 60 * 24,
+            max_messages: Some(1024),
+            max_total_size: Some(32 * 1024 * 1024),
+            max_message_size: Some(1024 * 1024),
+            compaction_delay: 60 * 60
+        }
+    }
+}
+
+impl Default for RouterConfig {
+    fn default() -> Self {
+        Self {
+            local_timeout: 60 * 5,
+            remote_timeout: 60 * 60,
+            local_eviction: Some(60 * 60 * 24 * 7),
+            remote_eviction: Some(60 * 60 * 24),
+            sweep_delay: 60 * 60
+        }
+    }
+}
+
+impl Config {
+    /// Read config from the given JSON file.
+    ///
+    /// If the file doesn't exist, then it will be
+    /// created with the default values.
+    pub async fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+
+        if !path.exists() {
+            let config = Self::default();
+
+            tokio::fs::write(path, serde_json::to_string_pretty(&config)?).await?;
+
+            return Ok(config);
+        }
+
+        let config = tokio::fs::read(path).await?;
+
+        Ok(serde_json::from_slice(&config)?)
+    }
+}
@@ -1,4 +1,374 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt;
+
+use hyperborealib::prelude::*;
+
+pub mod config;
+
+use config::Config;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+type Driver = ServerDriver<GlobalTableRouter, BfsRecursionTraversal, AppendLogMessagesInbox>;
+
+/// Maximal time given to the running tasks
+/// to finish their work on shutdown.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[tokio::main]
-async fn main() {
-    todo!()
+async fn main() -> anyhow::Result<()> {
+    env_logger::builder()
+        .default_format()
+        .filter_level({
+            if cfg!(debug_assertions) {
+                log::LevelFilter::max()
+            } else {
+                log::LevelFilter::Info
+            }
+        })
+        .parse_default_env()
+        .init();
+
+    let config_path = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("config.json"));
+
+    let config = Config::load(&config_path).await?;
+
+    tokio::fs::create_dir_all(&config.storage.folder).await?;
+
+    let secret_key = load_secret_key(config.storage.folder.join("secret_key")).await?;
+
+    log::info!("Hyperborea server v{VERSION}");
+    log::info!("  Hyperborealib v{HYPERBOREALIB_VERSION}");
+    log::info!("  Standard v{HYPERBOREALIB_STANDARD}");
+    log::info!("");
+    log::info!("  Config          : {config_path:?}");
+    log::info!("  Storage         : {:?}", &config.storage.folder);
+    log::info!("  Public key      : {}", secret_key.public_key().to_base64());
+    log::info!("  Local address   : {}", &config.server.local_address);
+    log::info!("  Exposed address : {}", &config.server.exposed_address);
+    log::info!("  Unix socket     : {:?}", &config.server.unix_socket);
+    log::info!("  TLS             : {}", config.server.tls.is_some());
+    log::info!("");
+
+    let http_client = match &config.server.ca_certificate {
+        Some(path) => ReqwestHttpClient::with_ca_certificate(tokio::fs::read(path).await?)?,
+        None => ReqwestHttpClient::default()
+    };
+
+    // Sent to all the tasks when the server should stop
+    let (shutdown_sender, shutdown) = tokio::sync::watch::channel(false);
+
+    let http_server = match &config.server.tls {
+        Some(tls) => AxumHttpServer::with_tls(&tls.certificate, &tls.private_key),
+        None => AxumHttpServer::default()
+    };
+
+    let http_server = http_server.with_shutdown_signal(wait_shutdown(shutdown.clone()));
+
+    // Build server driver
+    let driver = ServerDriver::new(
+        GlobalTableRouter::new(config.storage.folder.join("routing")).await?
+            .with_availability(RouterAvailability {
+                local_timeout: Duration::from_secs(config.router.local_timeout),
+                remote_timeout: Duration::from_secs(config.router.remote_timeout),
+                local_eviction: config.router.local_eviction.map(Duration::from_secs),
+                remote_eviction: config.router.remote_eviction.map(Duration::from_secs)
+            }),
+        BfsRecursionTraversal {
+            harvest_clients: config.bootstrap.harvest_clients,
+            ..BfsRecursionTraversal::default()
+        },
+        AppendLogMessagesInbox::new(
+            config.storage.folder.join("inbox"),
+            Duration::from_secs(config.inbox.lifetime),
+            MessagesInboxLimits {
+                max_messages: config.inbox.max_messages,
+                max_total_size: config.inbox.max_total_size,
+                max_message_size: config.inbox.max_message_size
+            }
+        ).await?,
+        ServerParams {
+            secret_key,
+            address: config.server.exposed_address.clone(),
+            request_lifetime: Duration::from_secs(config.server.request_lifetime),
+            replay_guard_capacity: config.server.replay_guard_capacity,
+            min_standard: config.server.min_standard,
+            forward_messages: config.server.forward_messages,
+            probe_announced_servers: config.server.probe_announced_servers,
+            max_poll_wait: Duration::from_secs(config.server.max_poll_wait),
+            max_lease: Duration::from_secs(config.server.max_lease),
+            max_batch_size: config.server.max_batch_size,
+            lookup_max_hops: config.server.lookup_max_hops,
+            lookup_timeout: Duration::from_secs(config.server.lookup_timeout)
+        }
+    );
+
+    let middleware = ServerMiddleware::new(
+        http_client.clone(),
+        http_server,
+        driver
+    ).await;
+
+    let driver = middleware.driver();
+
+    // Start HTTP server on the Unix domain socket
+    let mut unix_server = match &config.server.unix_socket {
+        #[cfg(unix)]
+        Some(path) => {
+            let mode = u32::from_str_radix(&config.server.unix_socket_mode, 8)?;
+
+            let server = middleware.http_server()
+                .clone()
+                .with_unix_socket_mode(mode);
+
+            let path = path.clone();
+
+            tokio::spawn(async move {
+                if let Err(err) = server.serve_unix(path).await {
+                    log::error!("Unix socket HTTP server error: {err}");
+                }
+            })
+        }
+
+        #[cfg(not(unix))]
+        Some(_) => anyhow::bail!("Unix domain sockets are not supported on this platform"),
+
+        None => tokio::spawn(std::future::pending())
+    };
+
+    // Start HTTP server
+    let mut server = tokio::spawn({
+        let local_address = config.server.local_address.clone();
+
+        async move {
+            if let Err(err) = middleware.serve(local_address).await {
+                log::error!("HTTP server error: {err}");
+            }
+        }
+    });
+
+    // Start inbox compaction
+    let mut compaction = tokio::spawn(compact_inbox(driver.clone(), config.inbox.compaction_delay, shutdown.clone()));
+
+    // Start routing table sweeping
+    let mut sweeping = tokio::spawn(sweep_router(driver.clone(), config.router.sweep_delay, shutdown.clone()));
+
+    // Start network traversal
+    let mut traversal = tokio::spawn(traverse(driver, http_client, config, shutdown));
+
+    tokio::select! {
+        _ = &mut server => log::error!("HTTP server stopped unexpectedly"),
+
+        _ = shutdown_signal() => log::info!("Shutting down"),
+
+        _ = &mut unix_server => log::error!("Unix socket HTTP server stopped unexpectedly"),
+
+        _ = &mut traversal => log::error!("Network traversal stopped unexpectedly"),
+        _ = &mut compaction => log::error!("Inbox compaction stopped unexpectedly"),
+        _ = &mut sweeping => log::error!("Routing table sweeping stopped unexpectedly")
+    }
+
+    // Let the tasks finish their current work
+    let _ = shutdown_sender.send(true);
+
+    let tasks = [&mut server, &mut unix_server, &mut traversal, &mut compaction, &mut sweeping];
+
+    let finished = tokio::time::timeout(SHUTDOWN_TIMEOUT, async {
+        for task in tasks {
+            if !task.is_finished() {
+                let _ = task.await;
+            }
+        }
+    }).await;
+
+    if finished.is_err() {
+        log::warn!("Tasks didn't stop in {} seconds, aborting them", SHUTDOWN_TIMEOUT.as_secs());
+
+        for task in [server, unix_server, traversal, compaction, sweeping] {
+            task.abort();
+        }
+    }
+
+    Ok(())
+}
+
+/// Read the server's secret key from the given file,
+/// or generate a new one and save it there.
+async fn load_secret_key(path: impl AsRef<Path>) -> anyhow::Result<SecretKey> {
+    let path = path.as_ref();
+
+    if path.exists() {
+        let secret_key = tokio::fs::read_to_string(path).await?;
+
+        return Ok(SecretKey::from_base64(secret_key.trim())?);
+    }
+
+    log::info!("Generating new server secret key");
+
+    let secret_key = SecretKey::random();
+
+    // Secret key must be readable only by the server's user
+    let mut options = tokio::fs::OpenOptions::new();
+
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file = options.open(path).await?;
+
+    file.write_all(secret_key.to_base64().as_bytes()).await?;
+    file.sync_all().await?;
+
+    Ok(secret_key)
+}
+
+/// Index bootstrap servers and traverse the network
+/// with the configured delay.
+async fn traverse(driver: Arc<Driver>, http_client: ReqwestHttpClient, config: Config, mut shutdown: Shutdown) {
+    let client = ClientMiddleware::new(
+        http_client,
+        driver.as_client()
+    );
+
+    let delay = Duration::from_secs(config.bootstrap.traversal_delay);
+
+    loop {
+        log::debug!("Indexing bootstrap addresses");
+
+        for address in &config.bootstrap.addresses {
+            if !address.is_http() {
+                log::warn!("Skipping bootstrap server {address}: only http and https addresses can be indexed");
+
+                continue;
+            }
+
+            match client.get_info(address).await {
+                Ok(info) => {
+                    let server = Server::new(info.public_key, address.clone());
+
+                    if let Err(err) = driver.router().index_server(server).await {
+                        log::error!("Failed to index bootstrap server {address}: {err}");
+                    }
+                }
+
+                Err(err) => log::warn!("Failed to request bootstrap server {address}: {err}")
+            }
+        }
+
+        log::debug!("Traversing network");
+
+        let report = driver.traversal().traverse(
+            client.http_client_ref().clone(),
+            &driver
+        ).await;
+
+        log::info!(
+            "Network traversal finished: {} servers found, {} clients indexed, {} unreachable",
+            report.found.len(),
+            report.clients.len(),
+            report.unreachable.len()
+        );
+
+        for err in &report.errors {
+            log::debug!("Traversal error: {err}");
+        }
+
+        if !sleep_or_shutdown(delay, &mut shutdown).await {
+            return;
+        }
+    }
+}
+
+/// Remove outdated messages from the inbox
+/// with the configured delay.
+async fn compact_inbox(driver: Arc<Driver>, delay: u64, mut shutdown: Shutdown) {
+    let delay = Duration::from_secs(delay);
+
+    while sleep_or_shutdown(delay, &mut shutdown).await {
+        log::debug!("Compacting inbox");
+
+        if let Err(err) = driver.messages_inbox().compact().await {
+            log::error!("Failed to compact inbox: {err}");
+        }
+    }
+}
+
+/// Remove idle clients from the routing table
+/// with the configured delay.
+async fn sweep_router(driver: Arc<Driver>, delay: u64, mut shutdown: Shutdown) {
+    let delay = Duration::from_secs(delay);
+
+    while sleep_or_shutdown(delay, &mut shutdown).await {
+        log::debug!("Sweeping routing table");
+
+        match driver.router().sweep().await {
+            Ok(0) => (),
+            Ok(evicted) => log::info!("Removed {evicted} idle clients from the routing table"),
+            Err(err) => log::error!("Failed to sweep routing table: {err}")
+        }
+    }
+}
+
+/// Receiver of the server's shutdown notification.
+type Shutdown = tokio::sync::watch::Receiver<bool>;
+
+/// Wait until the server is shutting down.
+async fn wait_shutdown(mut shutdown: Shutdown) {
+    // Sender is never dropped before sending the notification
+    let _ = shutdown.wait_for(|stopped| *stopped).await;
+}
+
+/// Wait for the given delay.
+/// 
+/// Return `false` if the server is shutting down.
+async fn sleep_or_shutdown(delay: Duration, shutdown: &mut Shutdown) -> bool {
+    tokio::select! {
+        biased;
+
+        _ = shutdown.wait_for(|stopped| *stopped) => false,
+        _ = tokio::time::sleep(delay) => true
+    }
+}
+
+/// Wait for SIGINT or SIGTERM signal.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            log::error!("Failed to listen for SIGINT: {err}");
+
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+
+            Err(err) => {
+                log::error!("Failed to listen for SIGTERM: {err}");
+
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => (),
+        _ = terminate => ()
+    }
 }
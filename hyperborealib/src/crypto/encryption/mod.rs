@@ -2,6 +2,8 @@ use super::Error;
 
 pub mod aes256_gcm;
 pub mod chacha20_poly1305;
+pub mod aes256_gcm_rn;
+pub mod xchacha20_poly1305;
 
 pub mod prelude {
     pub use super::Encryption;
@@ -15,12 +17,29 @@ pub mod prelude {
         encrypt as chacha20_poly1305_encrypt,
         decrypt as chacha20_poly1305_decrypt
     };
+
+    pub use super::aes256_gcm_rn::{
+        encrypt as aes256_gcm_rn_encrypt,
+        decrypt as aes256_gcm_rn_decrypt
+    };
+
+    pub use super::xchacha20_poly1305::{
+        encrypt as xchacha20_poly1305_encrypt,
+        decrypt as xchacha20_poly1305_decrypt
+    };
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// General data encryption implementation.
 /// 
+/// `aes256-gcm` and `chacha20-poly1305` algorithms use standard
+/// pre-defined nonces and are kept for compatibility. Encrypting
+/// many messages with the same key and nonce breaks their security,
+/// so `aes256-gcm-rn` and `xchacha20-poly1305` are preferred. They
+/// generate random nonce for every message and prepend it
+/// to the encrypted data.
+/// 
 /// ```rust
 /// use std::str::FromStr;
 /// 
@@ -29,23 +48,32 @@ pub mod prelude {
 /// assert_eq!(Encryption::None.to_string(),             "plain");
 /// assert_eq!(Encryption::Aes256Gcm.to_string(),        "aes256-gcm");
 /// assert_eq!(Encryption::ChaCha20Poly1305.to_string(), "chacha20-poly1305");
+/// assert_eq!(Encryption::Aes256GcmRn.to_string(),       "aes256-gcm-rn");
+/// assert_eq!(Encryption::XChaCha20Poly1305.to_string(), "xchacha20-poly1305");
 /// 
-/// assert_eq!(Encryption::from_str("none").unwrap(),              Encryption::None);
-/// assert_eq!(Encryption::from_str("plain").unwrap(),             Encryption::None);
-/// assert_eq!(Encryption::from_str("aes256-gcm").unwrap(),        Encryption::Aes256Gcm);
-/// assert_eq!(Encryption::from_str("chacha20-poly1305").unwrap(), Encryption::ChaCha20Poly1305);
+/// assert_eq!(Encryption::from_str("none").unwrap(),               Encryption::None);
+/// assert_eq!(Encryption::from_str("plain").unwrap(),              Encryption::None);
+/// assert_eq!(Encryption::from_str("aes256-gcm").unwrap(),         Encryption::Aes256Gcm);
+/// assert_eq!(Encryption::from_str("chacha20-poly1305").unwrap(),  Encryption::ChaCha20Poly1305);
+/// assert_eq!(Encryption::from_str("aes256-gcm-rn").unwrap(),      Encryption::Aes256GcmRn);
+/// assert_eq!(Encryption::from_str("xchacha20-poly1305").unwrap(), Encryption::XChaCha20Poly1305);
 /// 
-/// assert_eq!("none".parse::<Encryption>().unwrap(),              Encryption::None);
-/// assert_eq!("plain".parse::<Encryption>().unwrap(),             Encryption::None);
-/// assert_eq!("aes256-gcm".parse::<Encryption>().unwrap(),        Encryption::Aes256Gcm);
-/// assert_eq!("chacha20-poly1305".parse::<Encryption>().unwrap(), Encryption::ChaCha20Poly1305);
+/// assert_eq!("none".parse::<Encryption>().unwrap(),               Encryption::None);
+/// assert_eq!("plain".parse::<Encryption>().unwrap(),              Encryption::None);
+/// assert_eq!("aes256-gcm".parse::<Encryption>().unwrap(),         Encryption::Aes256Gcm);
+/// assert_eq!("chacha20-poly1305".parse::<Encryption>().unwrap(),  Encryption::ChaCha20Poly1305);
+/// assert_eq!("aes256-gcm-rn".parse::<Encryption>().unwrap(),      Encryption::Aes256GcmRn);
+/// assert_eq!("xchacha20-poly1305".parse::<Encryption>().unwrap(), Encryption::XChaCha20Poly1305);
 /// ```
 pub enum Encryption {
     #[default]
     None,
 
     Aes256Gcm,
-    ChaCha20Poly1305
+    ChaCha20Poly1305,
+
+    Aes256GcmRn,
+    XChaCha20Poly1305
 }
 
 impl Encryption {
@@ -73,6 +101,12 @@ impl Encryption {
                 .map_err(|err| Error::Encryption(err.into())),
 
             Self::ChaCha20Poly1305 => chacha20_poly1305::encrypt(data, secret)
+                .map_err(|err| Error::Encryption(err.into())),
+
+            Self::Aes256GcmRn => aes256_gcm_rn::encrypt(data, secret)
+                .map_err(|err| Error::Encryption(err.into())),
+
+            Self::XChaCha20Poly1305 => xchacha20_poly1305::encrypt(data, secret)
                 .map_err(|err| Error::Encryption(err.into()))
         }
     }
@@ -104,6 +138,12 @@ impl Encryption {
                 .map_err(|err| Error::Decryption(err.into())),
 
             Self::ChaCha20Poly1305 => chacha20_poly1305::decrypt(data, secret)
+                .map_err(|err| Error::Decryption(err.into())),
+
+            Self::Aes256GcmRn => aes256_gcm_rn::decrypt(data, secret)
+                .map_err(|err| Error::Decryption(err.into())),
+
+            Self::XChaCha20Poly1305 => xchacha20_poly1305::decrypt(data, secret)
                 .map_err(|err| Error::Decryption(err.into()))
         }
     }
@@ -118,6 +158,8 @@ impl std::str::FromStr for Encryption {
 
             "aes256-gcm" => Ok(Self::Aes256Gcm),
             "chacha20-poly1305"  => Ok(Self::ChaCha20Poly1305),
+            "aes256-gcm-rn" => Ok(Self::Aes256GcmRn),
+            "xchacha20-poly1305" => Ok(Self::XChaCha20Poly1305),
 
             _ => Err(Error::UnknownEncryption(value.to_string()))
         }
@@ -127,9 +169,11 @@ impl std::str::FromStr for Encryption {
 impl std::fmt::Display for Encryption {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::None             => write!(f, "plain"),
-            Self::Aes256Gcm        => write!(f, "aes256-gcm"),
-            Self::ChaCha20Poly1305 => write!(f, "chacha20-poly1305")
+            Self::None              => write!(f, "plain"),
+            Self::Aes256Gcm         => write!(f, "aes256-gcm"),
+            Self::ChaCha20Poly1305  => write!(f, "chacha20-poly1305"),
+            Self::Aes256GcmRn       => write!(f, "aes256-gcm-rn"),
+            Self::XChaCha20Poly1305 => write!(f, "xchacha20-poly1305")
         }
     }
 }
@@ -142,7 +186,9 @@ mod tests {
         &[
             (Encryption::None,             "plain"),
             (Encryption::Aes256Gcm,        "aes256-gcm"),
-            (Encryption::ChaCha20Poly1305, "chacha20-poly1305")
+            (Encryption::ChaCha20Poly1305,  "chacha20-poly1305"),
+            (Encryption::Aes256GcmRn,       "aes256-gcm-rn"),
+            (Encryption::XChaCha20Poly1305, "xchacha20-poly1305")
         ]
     }
 
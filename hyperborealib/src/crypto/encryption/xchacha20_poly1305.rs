@@ -0,0 +1,103 @@
+use chacha20poly1305::{XChaCha20Poly1305, Key, XNonce, KeyInit, Error};
+use chacha20poly1305::aead::Aead;
+
+use rand_chacha::ChaCha20Rng;
+use rand_chacha::rand_core::{SeedableRng, RngCore};
+
+/// Size of the random nonce prepended
+/// to the encrypted data.
+pub const NONCE_SIZE: usize = 24;
+
+/// Encrypt given value.
+/// 
+/// This function will generate random nonce
+/// and prepend it to the encrypted data.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use hyperborealib::crypto::encryption::xchacha20_poly1305;
+/// 
+/// let original = b"Hello, World!";
+/// let secret   = b"32 bytes long secret key .......";
+/// 
+/// let encrypted_1 = xchacha20_poly1305::encrypt(original, &secret).unwrap();
+/// let encrypted_2 = xchacha20_poly1305::encrypt(original, &secret).unwrap();
+/// 
+/// assert_ne!(encrypted_1, original);
+/// assert_ne!(encrypted_1, encrypted_2);
+/// ```
+pub fn encrypt(data: impl AsRef<[u8]>, secret: &[u8; 32]) -> Result<Vec<u8>, Error> {
+    let key = Key::from_slice(secret);
+
+    let mut nonce = [0; NONCE_SIZE];
+
+    ChaCha20Rng::from_entropy().fill_bytes(&mut nonce);
+
+    let encrypted = XChaCha20Poly1305::new(key)
+        .encrypt(XNonce::from_slice(&nonce), data.as_ref())?;
+
+    let mut result = Vec::with_capacity(NONCE_SIZE + encrypted.len());
+
+    result.extend_from_slice(&nonce);
+    result.extend(encrypted);
+
+    Ok(result)
+}
+
+/// Decrypt given value.
+/// 
+/// This function will read the nonce
+/// prepended to the encrypted data.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use hyperborealib::crypto::encryption::xchacha20_poly1305;
+/// 
+/// let original = b"Hello, World!";
+/// let secret   = b"32 bytes long secret key .......";
+/// 
+/// let encrypted = xchacha20_poly1305::encrypt(original, &secret).unwrap();
+/// let decrypted = xchacha20_poly1305::decrypt(&encrypted, &secret).unwrap();
+/// 
+/// assert_ne!(encrypted, original);
+/// assert_eq!(decrypted, original);
+/// ```
+pub fn decrypt(data: impl AsRef<[u8]>, secret: &[u8; 32]) -> Result<Vec<u8>, Error> {
+    let data = data.as_ref();
+
+    if data.len() < NONCE_SIZE {
+        return Err(Error);
+    }
+
+    let key = Key::from_slice(secret);
+
+    XChaCha20Poly1305::new(key)
+        .decrypt(XNonce::from_slice(&data[..NONCE_SIZE]), &data[NONCE_SIZE..])
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn encrypt_decrypt() -> Result<(), Error> {
+        let key = b"amogus aboba banana aboba amogus";
+
+        assert_eq!(decrypt(encrypt(b"Hello, World!", key)?, key)?, b"Hello, World!");
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn random_nonce() -> Result<(), Error> {
+        let key = b"amogus aboba banana aboba amogus";
+
+        assert_ne!(encrypt(b"Hello, World!", key)?, encrypt(b"Hello, World!", key)?);
+
+        assert!(decrypt([], key).is_err());
+
+        Ok(())
+    }
+}
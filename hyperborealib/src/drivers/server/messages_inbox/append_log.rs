@@ -0,0 +1,855 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::{json, Value as Json};
+
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+use crate::time::timestamp;
+
+use crate::crypto::prelude::*;
+use crate::rest_api::prelude::*;
+
+use super::{MessagesInbox, MessagesInboxLimits, MessagesInboxNotifier, MessagesNotification, Error};
+
+/// Minimal amount of outdated records in the receiver's
+/// log file needed to start its compaction.
+const COMPACTION_THRESHOLD: u64 = 128;
+
+#[derive(Debug, Default)]
+struct ReceiverLog {
+    /// Identifier of the next added message.
+    next_id: u64,
+
+    /// Stored messages with their identifiers.
+    messages: Vec<(u64, MessageInfo)>,
+
+    /// Leased messages. Delivery id to the message's
+    /// identifier and timestamp until which it's hidden.
+    /// 
+    /// Leases are not stored in the log file, so leased
+    /// messages are returned to the inbox after restart.
+    leases: HashMap<u64, (u64, u64)>,
+
+    /// Amount of records in the log file
+    /// which don't store actual messages.
+    outdated_records: u64,
+
+    /// Log was removed from the inbox while
+    /// other tasks were waiting for its lock.
+    removed: bool
+}
+
+#[derive(Debug, Clone)]
+/// Append Log Messages Inbox stores messages of every
+/// receiver in a separate append-only log file within
+/// the given folder.
+/// 
+/// Every accepted message is written and synced to the disk
+/// before the request is answered, so messages survive
+/// server restarts and crashes. Polled messages are marked
+/// as deleted by appending a new record to the log.
+/// 
+/// Log files are compacted (rewritten with only the stored
+/// messages) when they contain too many outdated records.
+/// Messages older than the inbox's lifetime are removed.
+/// 
+/// Every receiver's log is locked separately, so disk
+/// operations of one receiver don't block the others.
+pub struct AppendLogMessagesInbox {
+    /// Path to the inbox logs folder.
+    pub storage_folder: PathBuf,
+
+    /// Lifetime of stored messages.
+    pub ttl: Duration,
+
+    pub limits: MessagesInboxLimits,
+
+    pub notifier: Arc<MessagesInboxNotifier>,
+
+    receivers: Arc<Mutex<HashMap<PublicKey, Arc<AsyncMutex<ReceiverLog>>>>>
+}
+
+impl AppendLogMessagesInbox {
+    /// Open inbox in the given folder.
+    /// 
+    /// This method will read all the stored log files,
+    /// remove outdated messages and compact them if needed.
+    pub async fn new(storage_folder: impl Into<PathBuf>, ttl: Duration, limits: MessagesInboxLimits) -> std::io::Result<Self> {
+        let storage_folder = storage_folder.into();
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(?limits, "Building new AppendLogMessagesInbox in {:?} with {} seconds lifetime", storage_folder, ttl.as_secs());
+
+        tokio::fs::create_dir_all(&storage_folder).await?;
+
+        let inbox = Self {
+            storage_folder,
+            ttl,
+            limits,
+            notifier: Arc::new(MessagesInboxNotifier::default()),
+            receivers: Arc::new(Mutex::new(HashMap::new()))
+        };
+
+        let mut receivers = HashMap::new();
+        let mut entries = tokio::fs::read_dir(&inbox.storage_folder).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+
+            if path.extension().and_then(|ext| ext.to_str()) != Some("log") {
+                continue;
+            }
+
+            let Some(receiver) = path.file_stem().and_then(|name| name.to_str()) else {
+                continue;
+            };
+
+            let Ok(receiver) = PublicKey::from_base64(receiver) else {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(?path, "Skipping inbox log file with invalid name");
+
+                continue;
+            };
+
+            let mut log = Self::read_log(&path).await?;
+
+            inbox.compact_log(&receiver, &mut log, true).await?;
+
+            if !log.messages.is_empty() {
+                receivers.insert(receiver, Arc::new(AsyncMutex::new(log)));
+            }
+        }
+
+        *inbox.receivers.lock().expect("Failed to lock inbox receivers") = receivers;
+
+        Ok(inbox)
+    }
+
+    /// Remove outdated messages from all the stored
+    /// inboxes and compact their log files.
+    /// 
+    /// Outdated messages are removed from the inbox when
+    /// it's accessed, so this method should be called
+    /// periodically only to free the disk space.
+    pub async fn compact(&self) -> std::io::Result<()> {
+        let receivers = self.receivers.lock()
+            .expect("Failed to lock inbox receivers")
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for receiver in receivers {
+            let Some(mut log) = self.lock_log(&receiver, false).await else {
+                continue;
+            };
+
+            self.compact_log(&receiver, &mut log, false).await?;
+
+            if log.messages.is_empty() {
+                self.remove_log(&receiver, &mut log);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lock the receiver's log.
+    /// 
+    /// New empty log is created if `create` is true,
+    /// otherwise `None` is returned for unknown receivers.
+    async fn lock_log(&self, receiver: &PublicKey, create: bool) -> Option<OwnedMutexGuard<ReceiverLog>> {
+        loop {
+            let log = {
+                let mut receivers = self.receivers.lock()
+                    .expect("Failed to lock inbox receivers");
+
+                if create {
+                    receivers.entry(receiver.clone())
+                        .or_default()
+                        .clone()
+                } else {
+                    receivers.get(receiver)?.clone()
+                }
+            };
+
+            let log = log.lock_owned().await;
+
+            // Log could be removed while we were waiting
+            // for the lock, so the receiver is looked up again
+            if !log.removed {
+                return Some(log);
+            }
+        }
+    }
+
+    /// Remove the locked receiver's log from the inbox.
+    fn remove_log(&self, receiver: &PublicKey, log: &mut ReceiverLog) {
+        log.removed = true;
+
+        self.receivers.lock()
+            .expect("Failed to lock inbox receivers")
+            .remove(receiver);
+    }
+
+    fn log_path(&self, receiver: &PublicKey) -> PathBuf {
+        self.storage_folder.join(format!("{}.log", receiver.to_base64()))
+    }
+
+    /// Read the receiver's log file.
+    /// 
+    /// Broken records (e.g. partially written because
+    /// of a server crash) are counted as outdated.
+    async fn read_log(path: &Path) -> std::io::Result<ReceiverLog> {
+        let content = tokio::fs::read(path).await?;
+
+        let mut log = ReceiverLog::default();
+        let mut deleted = HashSet::new();
+
+        // Force compaction if the last record is not finished,
+        // otherwise the next appended record would be broken
+        if !content.is_empty() && !content.ends_with(b"\n") {
+            log.outdated_records = COMPACTION_THRESHOLD;
+        }
+
+        for line in content.split(|byte| *byte == b'\n') {
+            if line.is_empty() {
+                continue;
+            }
+
+            log.outdated_records += 1;
+
+            let Ok(record) = serde_json::from_slice::<Json>(line) else {
+                continue;
+            };
+
+            if let Some(add) = record.get("add") {
+                let Some(id) = add.get("id").and_then(Json::as_u64) else {
+                    continue;
+                };
+
+                let Ok(info) = MessageInfo::from_json(&add["info"]) else {
+                    continue;
+                };
+
+                log.next_id = log.next_id.max(id + 1);
+                log.messages.push((id, info));
+            }
+
+            else if let Some(ids) = record.get("delete").and_then(Json::as_array) {
+                deleted.extend(ids.iter().filter_map(Json::as_u64));
+            }
+        }
+
+        log.messages.retain(|(id, _)| !deleted.contains(id));
+        log.outdated_records -= log.messages.len() as u64;
+
+        Ok(log)
+    }
+
+    /// Append records to the receiver's log file
+    /// and sync it to the disk.
+    /// 
+    /// If writing fails the file is truncated back to its
+    /// previous length, so partially written records don't
+    /// break the records appended later.
+    async fn append_records(&self, receiver: &PublicKey, records: &[Json]) -> std::io::Result<()> {
+        let mut buf = Vec::new();
+
+        for record in records {
+            serde_json::to_writer(&mut buf, record)?;
+
+            buf.push(b'\n');
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.log_path(receiver))
+            .await?;
+
+        let length = file.metadata().await?.len();
+
+        let result = async {
+            file.write_all(&buf).await?;
+            file.sync_data().await
+        }.await;
+
+        if let Err(err) = result {
+            if let Err(_err) = file.set_len(length).await {
+                #[cfg(feature = "tracing")]
+                tracing::error!(receiver = receiver.to_base64(), "Failed to truncate inbox log: {_err}");
+            }
+
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Remove outdated messages from the receiver's log.
+    /// 
+    /// Amount of removed messages is added to
+    /// the log's outdated records counter.
+    fn expire_messages(&self, log: &mut ReceiverLog) {
+        let Some(min_received_at) = timestamp().checked_sub(self.ttl.as_secs()) else {
+            return;
+        };
+
+        let stored = log.messages.len();
+
+        log.messages.retain(|(_, info)| info.received_at >= min_received_at);
+
+        log.outdated_records += (stored - log.messages.len()) as u64;
+    }
+
+    /// Return messages with expired leases to the receiver's inbox.
+    fn expire_leases(log: &mut ReceiverLog) {
+        let now = timestamp();
+
+        let ReceiverLog { messages, leases, .. } = log;
+
+        leases.retain(|_, (id, leased_until)| {
+            *leased_until > now && messages.iter().any(|(message_id, _)| message_id == id)
+        });
+    }
+
+    /// Find visible (not leased) messages in the given channel.
+    /// 
+    /// Return indexes of found messages in the log.
+    fn find_messages(log: &ReceiverLog, channel: &str, limit: Option<u64>) -> Vec<usize> {
+        let leased = log.leases.values()
+            .map(|(id, _)| *id)
+            .collect::<HashSet<_>>();
+
+        log.messages.iter()
+            .enumerate()
+            .filter(|(_, (id, info))| info.channel == channel && !leased.contains(id))
+            .map(|(i, _)| i)
+            .take(limit.unwrap_or(u64::MAX) as usize)
+            .collect()
+    }
+
+    /// Rewrite the receiver's log file with only stored messages.
+    /// 
+    /// Log file is rewritten only if it has enough outdated
+    /// records, or if `force` is true and there's any.
+    /// The file is removed if there's no stored messages left.
+    async fn compact_log(&self, receiver: &PublicKey, log: &mut ReceiverLog, force: bool) -> std::io::Result<()> {
+        self.expire_messages(log);
+
+        if log.outdated_records == 0 {
+            return Ok(());
+        }
+
+        let compact = force || log.messages.is_empty() ||
+            log.outdated_records >= COMPACTION_THRESHOLD && log.outdated_records >= log.messages.len() as u64;
+
+        if !compact {
+            return Ok(());
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            receiver = receiver.to_base64(),
+            messages = log.messages.len(),
+            outdated_records = log.outdated_records,
+            "Compacting inbox log"
+        );
+
+        let path = self.log_path(receiver);
+
+        if log.messages.is_empty() {
+            tokio::fs::remove_file(&path).await?;
+        }
+
+        else {
+            let mut buf = Vec::new();
+
+            for (id, info) in &log.messages {
+                let record = json!({
+                    "add": {
+                        "id": id,
+                        "info": info.to_json().map_err(std::io::Error::other)?
+                    }
+                });
+
+                serde_json::to_writer(&mut buf, &record)?;
+
+                buf.push(b'\n');
+            }
+
+            // Write new log to the temporary file and atomically
+            // replace the old one so it's never partially written
+            let temp_path = path.with_extension("log.tmp");
+
+            let mut file = tokio::fs::File::create(&temp_path).await?;
+
+            file.write_all(&buf).await?;
+            file.sync_all().await?;
+
+            tokio::fs::rename(&temp_path, &path).await?;
+        }
+
+        // Sync the folder to persist the file replacement
+        #[cfg(unix)]
+        tokio::fs::File::open(&self.storage_folder).await?
+            .sync_all().await?;
+
+        log.outdated_records = 0;
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl MessagesInbox for AppendLogMessagesInbox {
+    async fn add_message(&self, sender: Sender, receiver: PublicKey, channel: String, message: Message) -> Result<(), Error> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            sender = ?sender,
+            receiver = receiver.to_base64(),
+            channel,
+            "Adding new message"
+        );
+
+        let mut log = self.lock_log(&receiver, true).await
+            .expect("Receiver's log must be created");
+
+        let log = &mut *log;
+
+        self.expire_messages(log);
+
+        let stored_size = log.messages.iter()
+            .map(|(_, info)| MessagesInboxLimits::message_size(&info.message))
+            .sum::<u64>();
+
+        self.limits.check(
+            MessagesInboxLimits::message_size(&message),
+            log.messages.len() as u64,
+            stored_size
+        )?;
+
+        let info = MessageInfo {
+            sender,
+            channel: channel.clone(),
+            message,
+            received_at: timestamp()
+        };
+
+        let record = json!({
+            "add": {
+                "id": log.next_id,
+                "info": info.to_json().map_err(|err| Error::Other(err.into()))?
+            }
+        });
+
+        self.append_records(&receiver, &[record]).await
+            .map_err(|err| Error::Other(err.into()))?;
+
+        log.messages.push((log.next_id, info));
+        log.next_id += 1;
+
+        self.notifier.notify(&receiver, &channel);
+
+        Ok(())
+    }
+
+    async fn poll_messages(&self, receiver: PublicKey, channel: String, limit: Option<u64>) -> (Vec<MessageInfo>, u64) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            receiver = receiver.to_base64(),
+            channel,
+            limit,
+            "Polling messages"
+        );
+
+        let Some(mut log) = self.lock_log(&receiver, false).await else {
+            return (vec![], 0);
+        };
+
+        let log = &mut *log;
+
+        self.expire_messages(log);
+
+        Self::expire_leases(log);
+
+        let mut messages = Vec::new();
+        let mut deleted = Vec::new();
+
+        for i in Self::find_messages(log, &channel, limit).into_iter().rev() {
+            let (id, info) = log.messages.remove(i);
+
+            deleted.push(id);
+            messages.push(info);
+        }
+
+        messages.reverse();
+        deleted.reverse();
+
+        let remained = (log.messages.len() - log.leases.len()) as u64;
+
+        if !deleted.is_empty() {
+            log.outdated_records += deleted.len() as u64 + 1;
+
+            // Polled messages could be returned again
+            // after restart if this record is lost
+            if let Err(_err) = self.append_records(&receiver, &[json!({ "delete": deleted })]).await {
+                #[cfg(feature = "tracing")]
+                tracing::error!(receiver = receiver.to_base64(), "Failed to store polled messages: {_err}");
+            }
+        }
+
+        if let Err(_err) = self.compact_log(&receiver, log, false).await {
+            #[cfg(feature = "tracing")]
+            tracing::error!(receiver = receiver.to_base64(), "Failed to compact inbox log: {_err}");
+        }
+
+        if log.messages.is_empty() && log.outdated_records == 0 {
+            self.remove_log(&receiver, log);
+        }
+
+        (messages, remained)
+    }
+
+    async fn lease_messages(&self, receiver: PublicKey, channel: String, limit: Option<u64>, timeout: Duration) -> (Vec<(u64, MessageInfo)>, u64) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            receiver = receiver.to_base64(),
+            channel,
+            limit,
+            timeout = timeout.as_secs(),
+            "Leasing messages"
+        );
+
+        let Some(mut log) = self.lock_log(&receiver, false).await else {
+            return (vec![], 0);
+        };
+
+        let log = &mut *log;
+
+        self.expire_messages(log);
+
+        Self::expire_leases(log);
+
+        let leased_until = timestamp().saturating_add(timeout.as_secs());
+
+        let mut messages = Vec::new();
+
+        for i in Self::find_messages(log, &channel, limit) {
+            let (id, info) = &log.messages[i];
+
+            let mut delivery_id = safe_random_u64();
+
+            while log.leases.contains_key(&delivery_id) {
+                delivery_id = safe_random_u64();
+            }
+
+            log.leases.insert(delivery_id, (*id, leased_until));
+
+            messages.push((delivery_id, info.clone()));
+        }
+
+        let remained = (log.messages.len() - log.leases.len()) as u64;
+
+        (messages, remained)
+    }
+
+    async fn ack_messages(&self, receiver: PublicKey, delivery_ids: Vec<u64>) -> u64 {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            receiver = receiver.to_base64(),
+            ?delivery_ids,
+            "Acknowledging messages"
+        );
+
+        let Some(mut log) = self.lock_log(&receiver, false).await else {
+            return 0;
+        };
+
+        let log = &mut *log;
+
+        self.expire_messages(log);
+
+        Self::expire_leases(log);
+
+        let deleted = delivery_ids.iter()
+            .filter_map(|delivery_id| log.leases.remove(delivery_id))
+            .map(|(id, _)| id)
+            .collect::<HashSet<_>>();
+
+        if deleted.is_empty() {
+            return 0;
+        }
+
+        log.messages.retain(|(id, _)| !deleted.contains(id));
+        log.outdated_records += deleted.len() as u64 + 1;
+
+        // Acknowledged messages could be returned again
+        // after restart if this record is lost
+        if let Err(_err) = self.append_records(&receiver, &[json!({ "delete": deleted })]).await {
+            #[cfg(feature = "tracing")]
+            tracing::error!(receiver = receiver.to_base64(), "Failed to store acknowledged messages: {_err}");
+        }
+
+        if let Err(_err) = self.compact_log(&receiver, log, false).await {
+            #[cfg(feature = "tracing")]
+            tracing::error!(receiver = receiver.to_base64(), "Failed to compact inbox log: {_err}");
+        }
+
+        if log.messages.is_empty() && log.outdated_records == 0 {
+            self.remove_log(&receiver, log);
+        }
+
+        deleted.len() as u64
+    }
+
+    async fn remove_inbox(&self, receiver: PublicKey) -> Vec<MessageInfo> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(receiver = receiver.to_base64(), "Removing inbox");
+
+        let Some(mut log) = self.lock_log(&receiver, false).await else {
+            return vec![];
+        };
+
+        // Remove the file while the log is still locked,
+        // otherwise new messages could be appended to it
+        // by a log created for the same receiver
+        if let Err(_err) = tokio::fs::remove_file(self.log_path(&receiver)).await {
+            #[cfg(feature = "tracing")]
+            tracing::error!(receiver = receiver.to_base64(), "Failed to remove inbox log: {_err}");
+        }
+
+        self.remove_log(&receiver, &mut log);
+
+        self.expire_messages(&mut log);
+
+        // Messages are stored in the order they were added
+        std::mem::take(&mut log.messages).into_iter()
+            .map(|(_, info)| info)
+            .collect()
+    }
+
+    #[inline]
+    fn subscribe(&self, receiver: &PublicKey, channel: &str, timeout: Duration) -> Option<MessagesNotification> {
+        Some(self.notifier.subscribe(receiver, channel, timeout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::rest_api::types::sender::tests::get_sender;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn persistence() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir()
+            .join("append-log-messages-inbox-test");
+
+        if path.exists() {
+            std::fs::remove_dir_all(&path)?;
+        }
+
+        let ttl = Duration::from_secs(60);
+        let limits = MessagesInboxLimits::default();
+
+        let receiver = SecretKey::random().public_key();
+        let encoding = MessageEncoding::from_str("base64").unwrap();
+
+        let inbox = AppendLogMessagesInbox::new(&path, ttl, limits).await?;
+
+        for i in 0..10 {
+            let message = Message::new(format!("Message {i}"), "", encoding);
+
+            inbox.add_message(get_sender(), receiver.clone(), String::from("test"), message).await?;
+        }
+
+        let (messages, remaining) = inbox.poll_messages(receiver.clone(), String::from("test"), Some(4)).await;
+
+        assert_eq!(messages.len(), 4);
+        assert_eq!(remaining, 6);
+
+        // Simulate broken record written during the crash
+        let mut file = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(path.join(format!("{}.log", receiver.to_base64())))
+            .await?;
+
+        file.write_all(b"{\"add\":{\"id\":").await?;
+
+        drop(file);
+        drop(inbox);
+
+        // Reopen the inbox
+        let inbox = AppendLogMessagesInbox::new(&path, ttl, limits).await?;
+
+        let message = Message::new("Message 10", "", encoding);
+
+        inbox.add_message(get_sender(), receiver.clone(), String::from("test"), message).await?;
+
+        let (messages, remaining) = inbox.poll_messages(receiver.clone(), String::from("test"), None).await;
+
+        assert_eq!(remaining, 0);
+
+        assert_eq!(
+            messages.iter().map(|info| info.message.content.as_str()).collect::<Vec<_>>(),
+            (4..=10).map(|i| format!("Message {i}")).collect::<Vec<_>>()
+        );
+
+        // Log file is removed when all the messages are polled
+        assert!(!path.join(format!("{}.log", receiver.to_base64())).exists());
+
+        tokio::fs::remove_dir_all(path).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn remove_inbox() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir()
+            .join("append-log-messages-inbox-remove-test");
+
+        if path.exists() {
+            std::fs::remove_dir_all(&path)?;
+        }
+
+        let receiver = SecretKey::random().public_key();
+        let encoding = MessageEncoding::from_str("base64").unwrap();
+
+        let inbox = AppendLogMessagesInbox::new(&path, Duration::from_secs(60), MessagesInboxLimits::default()).await?;
+
+        for channel in ["first", "second"] {
+            let message = Message::new(channel, "", encoding);
+
+            inbox.add_message(get_sender(), receiver.clone(), String::from(channel), message).await?;
+        }
+
+        // Leased messages are removed as well
+        inbox.lease_messages(receiver.clone(), String::from("first"), None, Duration::from_secs(60)).await;
+
+        let messages = inbox.remove_inbox(receiver.clone()).await;
+
+        assert_eq!(
+            messages.iter().map(|info| info.channel.as_str()).collect::<Vec<_>>(),
+            ["first", "second"]
+        );
+
+        assert!(!path.join(format!("{}.log", receiver.to_base64())).exists());
+        assert!(inbox.remove_inbox(receiver).await.is_empty());
+
+        tokio::fs::remove_dir_all(path).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn concurrent_remove_inbox() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir()
+            .join("append-log-messages-inbox-concurrent-remove-test");
+
+        if path.exists() {
+            std::fs::remove_dir_all(&path)?;
+        }
+
+        let receiver = SecretKey::random().public_key();
+        let encoding = MessageEncoding::from_str("base64").unwrap();
+
+        let inbox = AppendLogMessagesInbox::new(&path, Duration::from_secs(60), MessagesInboxLimits::default()).await?;
+
+        let log_path = inbox.log_path(&receiver);
+
+        for i in 0..32 {
+            let message = Message::new(format!("Message {i}"), "", encoding);
+
+            inbox.add_message(get_sender(), receiver.clone(), String::from("test"), message.clone()).await?;
+
+            // Add new message while the inbox is being removed
+            let (_, added) = tokio::join!(
+                inbox.remove_inbox(receiver.clone()),
+                inbox.add_message(get_sender(), receiver.clone(), String::from("test"), message)
+            );
+
+            added?;
+
+            // Messages left in the inbox must be stored in its log file
+            let stored = match inbox.lock_log(&receiver, false).await {
+                Some(log) => log.messages.len(),
+                None => 0
+            };
+
+            let persisted = if log_path.exists() {
+                AppendLogMessagesInbox::read_log(&log_path).await?.messages.len()
+            } else {
+                0
+            };
+
+            assert_eq!(stored, persisted);
+        }
+
+        tokio::fs::remove_dir_all(path).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lease() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir()
+            .join("append-log-messages-inbox-lease-test");
+
+        if path.exists() {
+            std::fs::remove_dir_all(&path)?;
+        }
+
+        let ttl = Duration::from_secs(60);
+        let limits = MessagesInboxLimits::default();
+
+        let receiver = SecretKey::random().public_key();
+        let encoding = MessageEncoding::from_str("base64").unwrap();
+
+        let inbox = AppendLogMessagesInbox::new(&path, ttl, limits).await?;
+
+        for i in 0..4 {
+            let message = Message::new(format!("Message {i}"), "", encoding);
+
+            inbox.add_message(get_sender(), receiver.clone(), String::from("test"), message).await?;
+        }
+
+        let (messages, remaining) = inbox.lease_messages(receiver.clone(), String::from("test"), Some(2), Duration::from_secs(60)).await;
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(remaining, 2);
+
+        // Leased messages are hidden
+        let (leased, remaining) = inbox.lease_messages(receiver.clone(), String::from("test"), Some(1), Duration::from_secs(60)).await;
+
+        assert_eq!(leased[0].1.message.content, "Message 2");
+        assert_eq!(remaining, 1);
+
+        assert_eq!(inbox.ack_messages(receiver.clone(), vec![messages[0].0, messages[1].0]).await, 2);
+        assert_eq!(inbox.ack_messages(receiver.clone(), vec![messages[0].0]).await, 0);
+
+        drop(inbox);
+
+        // Not acknowledged messages are returned after restart
+        let inbox = AppendLogMessagesInbox::new(&path, ttl, limits).await?;
+
+        let (messages, remaining) = inbox.poll_messages(receiver.clone(), String::from("test"), None).await;
+
+        assert_eq!(remaining, 0);
+
+        assert_eq!(
+            messages.iter().map(|info| info.message.content.as_str()).collect::<Vec<_>>(),
+            ["Message 2", "Message 3"]
+        );
+
+        tokio::fs::remove_dir_all(path).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use crate::rest_api::prelude::*;
+
+use super::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Limits of the messages stored for a single receiver.
+/// 
+/// `None` values mean that the limit is disabled.
+pub struct MessagesInboxLimits {
+    /// Maximal amount of messages stored for one receiver.
+    pub max_messages: Option<u64>,
+
+    /// Maximal total size in bytes of all the messages
+    /// stored for one receiver.
+    pub max_total_size: Option<u64>,
+
+    /// Maximal size in bytes of a single message.
+    pub max_message_size: Option<u64>
+}
+
+impl Default for MessagesInboxLimits {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_messages: Some(1024),
+            max_total_size: Some(32 * 1024 * 1024),
+            max_message_size: Some(1024 * 1024)
+        }
+    }
+}
+
+impl MessagesInboxLimits {
+    #[inline]
+    /// Limits which allow to store any amount of messages.
+    pub fn unlimited() -> Self {
+        Self {
+            max_messages: None,
+            max_total_size: None,
+            max_message_size: None
+        }
+    }
+
+    #[inline]
+    /// Get size of the message in bytes.
+    /// 
+    /// It is the total length of the message's
+    /// encoded content and sign.
+    pub fn message_size(message: &Message) -> u64 {
+        (message.content.len() + message.sign.len()) as u64
+    }
+
+    /// Verify that the message can be added to the receiver's inbox.
+    /// 
+    /// - `message_size` must contain size of the new message.
+    /// 
+    /// - `stored_messages` must contain amount of messages
+    ///   already stored in the receiver's inbox.
+    /// 
+    /// - `stored_size` must contain total size of messages
+    ///   already stored in the receiver's inbox.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use hyperborealib::drivers::server::messages_inbox::*;
+    /// 
+    /// let limits = MessagesInboxLimits {
+    ///     max_messages: Some(2),
+    ///     max_total_size: None,
+    ///     max_message_size: Some(1024)
+    /// };
+    /// 
+    /// assert!(limits.check(512, 1, 512).is_ok());
+    /// 
+    /// assert!(matches!(limits.check(2048, 0, 0), Err(Error::MessageTooLarge { .. })));
+    /// assert!(matches!(limits.check(512, 2, 1024), Err(Error::InboxFull)));
+    /// ```
+    pub fn check(&self, message_size: u64, stored_messages: u64, stored_size: u64) -> Result<(), Error> {
+        if let Some(limit) = self.max_message_size {
+            if message_size > limit {
+                return Err(Error::MessageTooLarge {
+                    size: message_size,
+                    limit
+                });
+            }
+        }
+
+        if let Some(limit) = self.max_messages {
+            if stored_messages >= limit {
+                return Err(Error::InboxFull);
+            }
+        }
+
+        if let Some(limit) = self.max_total_size {
+            if stored_size + message_size > limit {
+                return Err(Error::InboxFull);
+            }
+        }
+
+        Ok(())
+    }
+}
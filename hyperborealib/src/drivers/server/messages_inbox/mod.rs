@@ -1,3 +1,7 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
 use crate::crypto::asymmetric::PublicKey;
 
 use crate::rest_api::prelude::*;
@@ -5,13 +9,50 @@ use crate::rest_api::prelude::*;
 #[cfg(feature = "inbox-stored-queue")]
 pub mod stored_queue;
 
+#[cfg(feature = "inbox-append-log")]
+pub mod append_log;
+
+#[cfg(any(feature = "inbox-stored-queue", feature = "inbox-append-log"))]
+mod notifier;
+
+mod limits;
+
+pub use limits::MessagesInboxLimits;
+
+#[cfg(any(feature = "inbox-stored-queue", feature = "inbox-append-log"))]
+pub use notifier::MessagesInboxNotifier;
+
+/// Future returned by the `MessagesInbox::subscribe` method.
+/// 
+/// Resolves to `true` if a new message was added
+/// to the inbox, or `false` if the timeout has expired.
+pub type MessagesNotification = Pin<Box<dyn Future<Output = bool> + Send>>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Receiver's inbox is full")]
+    InboxFull,
+
+    #[error("Message is too large: {size} bytes, limit is {limit} bytes")]
+    MessageTooLarge {
+        size: u64,
+        limit: u64
+    },
+
+    #[error(transparent)]
+    Other(#[from] Box<dyn std::error::Error + Send + Sync>)
+}
+
 #[async_trait::async_trait]
 /// MessagesQueue is a struct that stores messages
 /// sent by external clients and meant to be read
 /// by local clients.
 pub trait MessagesInbox {
     /// Add new message to the inbox.
-    async fn add_message(&self, sender: Sender, receiver: PublicKey, channel: String, message: Message);
+    /// 
+    /// This method will return an error if the message
+    /// can't be stored because of the inbox limits.
+    async fn add_message(&self, sender: Sender, receiver: PublicKey, channel: String, message: Message) -> Result<(), Error>;
 
     /// Read client's inbox, applying given filters.
     /// 
@@ -19,4 +60,49 @@ pub trait MessagesInbox {
     /// 
     /// This method will remove read messages from the inbox.
     async fn poll_messages(&self, receiver: PublicKey, channel: String, limit: Option<u64>) -> (Vec<MessageInfo>, u64);
+
+    /// Lease client's messages, applying given filters.
+    /// 
+    /// Return list of leased messages with their delivery
+    /// ids and number of remained (not leased) messages.
+    /// 
+    /// Leased messages are hidden from the inbox
+    /// for the given timeout. They're removed only when
+    /// acknowledged using `ack_messages` method, otherwise
+    /// they will be returned to the inbox after timeout.
+    async fn lease_messages(&self, receiver: PublicKey, channel: String, limit: Option<u64>, timeout: Duration) -> (Vec<(u64, MessageInfo)>, u64);
+
+    /// Acknowledge leased messages, removing them
+    /// from the inbox.
+    /// 
+    /// Return number of removed messages. Unknown delivery ids
+    /// and ids of messages with expired leases are ignored.
+    async fn ack_messages(&self, receiver: PublicKey, delivery_ids: Vec<u64>) -> u64;
+
+    /// Remove all the client's messages from the inbox,
+    /// including the leased ones.
+    /// 
+    /// Return list of removed messages ordered
+    /// by their receiving time.
+    /// 
+    /// This is used to drop or return the inbox of clients
+    /// disconnected by the `POST /api/v1/disconnect` request.
+    async fn remove_inbox(&self, receiver: PublicKey) -> Vec<MessageInfo>;
+
+    /// Subscribe to the new messages of the receiver's channel.
+    /// 
+    /// Returned future resolves when a new message is added
+    /// to the receiver's channel after this method was called,
+    /// or when the timeout expires. It should be obtained before
+    /// polling the inbox so messages added in between are not missed.
+    /// 
+    /// This is used to hold `POST /api/v1/poll` requests
+    /// with the `wait` field set until messages arrive.
+    /// 
+    /// Return `None` if the inbox doesn't support notifications.
+    fn subscribe(&self, receiver: &PublicKey, channel: &str, timeout: Duration) -> Option<MessagesNotification> {
+        let _ = (receiver, channel, timeout);
+
+        None
+    }
 }
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::sync::watch;
+
+use crate::crypto::asymmetric::PublicKey;
+
+use super::MessagesNotification;
+
+#[derive(Debug, Default)]
+/// Helper struct used by the messages inboxes
+/// to implement the `MessagesInbox::subscribe` method.
+/// 
+/// Inbox should call `notify` every time a new message
+/// is added to the receiver's channel.
+pub struct MessagesInboxNotifier {
+    channels: Mutex<HashMap<(PublicKey, String), watch::Sender<()>>>
+}
+
+impl MessagesInboxNotifier {
+    /// Subscribe to the new messages of the receiver's channel.
+    /// 
+    /// Returned future resolves to `true` when a new message
+    /// is added after this method was called, or to `false`
+    /// when the timeout expires.
+    pub fn subscribe(&self, receiver: &PublicKey, channel: &str, timeout: Duration) -> MessagesNotification {
+        let mut listener = {
+            let mut channels = self.channels.lock()
+                .expect("Failed to lock messages inbox notifier");
+
+            // Remove channels without listeners
+            channels.retain(|_, sender| sender.receiver_count() > 0);
+
+            channels.entry((receiver.clone(), channel.to_string()))
+                .or_insert_with(|| watch::channel(()).0)
+                .subscribe()
+        };
+
+        Box::pin(async move {
+            matches!(tokio::time::timeout(timeout, listener.changed()).await, Ok(Ok(())))
+        })
+    }
+
+    /// Wake up all the listeners of the receiver's channel.
+    pub fn notify(&self, receiver: &PublicKey, channel: &str) {
+        let mut channels = self.channels.lock()
+            .expect("Failed to lock messages inbox notifier");
+
+        let key = (receiver.clone(), channel.to_string());
+
+        if let Some(sender) = channels.get(&key) {
+            // Error means that there are no listeners
+            if sender.send(()).is_err() {
+                channels.remove(&key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypto::prelude::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn notify() {
+        let notifier = MessagesInboxNotifier::default();
+
+        let receiver = SecretKey::random().public_key();
+
+        // Timeout
+        assert!(!notifier.subscribe(&receiver, "channel", Duration::from_millis(10)).await);
+
+        // Notification sent before awaiting is not missed
+        let notification = notifier.subscribe(&receiver, "channel", Duration::from_secs(5));
+
+        notifier.notify(&receiver, "channel");
+
+        assert!(notification.await);
+
+        // Other channels don't wake the listener
+        let notification = notifier.subscribe(&receiver, "channel", Duration::from_millis(10));
+
+        notifier.notify(&receiver, "another channel");
+
+        assert!(!notification.await);
+    }
+}
@@ -1,42 +1,137 @@
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use moka::future::Cache;
 
 use crate::time::timestamp;
 
-use crate::crypto::asymmetric::PublicKey;
+use crate::crypto::prelude::*;
 use crate::rest_api::prelude::*;
 
-use super::MessagesInbox;
+use super::{MessagesInbox, MessagesInboxLimits, MessagesInboxNotifier, MessagesNotification, Error};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Message leased by the receiver.
+pub struct LeasedMessage {
+    pub delivery_id: u64,
+
+    /// Timestamp until which the message is hidden.
+    pub leased_until: u64,
+
+    pub info: MessageInfo
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+/// Messages stored for a single receiver.
+pub struct ReceiverInbox {
+    pub messages: Vec<MessageInfo>,
+    pub leased: Vec<LeasedMessage>
+}
+
+impl ReceiverInbox {
+    /// Return messages which leases have expired to the inbox.
+    fn return_expired(&mut self) {
+        let now = timestamp();
+
+        if self.leased.iter().any(|message| message.leased_until <= now) {
+            // Expired messages were read before others, so they're put first
+            let expired = self.leased.iter()
+                .filter(|message| message.leased_until <= now)
+                .map(|message| message.info.clone())
+                .collect::<Vec<_>>();
+
+            self.messages.splice(0..0, expired);
+
+            self.leased.retain(|message| message.leased_until > now);
+        }
+    }
+
+    /// Remove messages from the inbox.
+    /// 
+    /// Return list of removed messages and number of remained.
+    fn take_messages(&mut self, channel: &str, limit: Option<u64>) -> (Vec<MessageInfo>, u64) {
+        self.return_expired();
+
+        let mut messages = Vec::new();
+
+        let mut limit = limit.unwrap_or(self.messages.len() as u64);
+        let mut i = 0;
+
+        while self.messages.len() > i && limit > 0 {
+            let record = &self.messages[i];
+
+            if record.channel == channel {
+                limit -= 1;
+
+                messages.push(self.messages.remove(i));
+            }
+
+            else {
+                i += 1;
+            }
+        }
+
+        (messages, self.messages.len() as u64)
+    }
+}
 
 #[derive(Debug, Clone)]
+/// Messages inbox stored in memory.
+/// 
+/// Every receiver's inbox is locked while it's modified,
+/// so concurrent requests of the same receiver can't
+/// overwrite each other's changes.
 pub struct StoredQueueMessagesInbox {
-    pub inbox: Cache<PublicKey, Vec<MessageInfo>>
+    pub inbox: Cache<PublicKey, Arc<Mutex<ReceiverInbox>>>,
+    pub limits: MessagesInboxLimits,
+    pub notifier: Arc<MessagesInboxNotifier>
 }
 
 impl Default for StoredQueueMessagesInbox {
     #[inline]
     fn default() -> Self {
-        Self::new(Duration::from_secs(60 * 60 * 24))
+        Self::new(Duration::from_secs(60 * 60 * 24), MessagesInboxLimits::default())
     }
 }
 
 impl StoredQueueMessagesInbox {
-    pub fn new(ttl: Duration) -> Self {
+    pub fn new(ttl: Duration, limits: MessagesInboxLimits) -> Self {
         #[cfg(feature = "tracing")]
-        tracing::trace!("Building new StoredQueueMessagesInbox with {} seconds lifetime", ttl.as_secs());
+        tracing::trace!(?limits, "Building new StoredQueueMessagesInbox with {} seconds lifetime", ttl.as_secs());
 
         Self {
             inbox: Cache::builder()
                 .time_to_idle(ttl)
-                .build()
+                .build(),
+
+            limits,
+            notifier: Arc::new(MessagesInboxNotifier::default())
         }
     }
+
+    /// Get the receiver's inbox, creating an empty one if needed.
+    async fn get_inbox(&self, receiver: &PublicKey) -> Arc<Mutex<ReceiverInbox>> {
+        self.inbox.get_with_by_ref(receiver, async { Arc::default() }).await
+    }
+
+    /// Remove messages from the receiver's inbox.
+    /// 
+    /// Return list of removed messages and number of remained.
+    async fn take_messages(&self, receiver: &PublicKey, channel: &str, limit: Option<u64>) -> (Vec<MessageInfo>, u64) {
+        let Some(inbox) = self.inbox.get(receiver).await else {
+            return (vec![], 0);
+        };
+
+        let mut inbox = inbox.lock()
+            .expect("Failed to lock receiver's inbox");
+
+        inbox.take_messages(channel, limit)
+    }
 }
 
 #[async_trait::async_trait]
 impl MessagesInbox for StoredQueueMessagesInbox {
-    async fn add_message(&self, sender: Sender, receiver: PublicKey, channel: String, message: Message) {
+    async fn add_message(&self, sender: Sender, receiver: PublicKey, channel: String, message: Message) -> Result<(), Error> {
         #[cfg(feature = "tracing")]
         tracing::debug!(
             sender = ?sender,
@@ -45,17 +140,36 @@ impl MessagesInbox for StoredQueueMessagesInbox {
             "Adding new message"
         );
 
-        let mut inbox = self.inbox.get(&receiver).await
-            .unwrap_or_default();
+        let inbox = self.get_inbox(&receiver).await;
 
-        inbox.push(MessageInfo {
-            sender,
-            channel,
-            message,
-            received_at: timestamp()
-        });
+        // Limits are checked under the same lock
+        // as the message is added with
+        {
+            let mut inbox = inbox.lock()
+                .expect("Failed to lock receiver's inbox");
 
-        self.inbox.insert(receiver, inbox).await;
+            let stored_size = inbox.messages.iter()
+                .chain(inbox.leased.iter().map(|message| &message.info))
+                .map(|info| MessagesInboxLimits::message_size(&info.message))
+                .sum::<u64>();
+
+            self.limits.check(
+                MessagesInboxLimits::message_size(&message),
+                (inbox.messages.len() + inbox.leased.len()) as u64,
+                stored_size
+            )?;
+
+            inbox.messages.push(MessageInfo {
+                sender,
+                channel: channel.clone(),
+                message,
+                received_at: timestamp()
+            });
+        }
+
+        self.notifier.notify(&receiver, &channel);
+
+        Ok(())
     }
 
     async fn poll_messages(&self, receiver: PublicKey, channel: String, limit: Option<u64>) -> (Vec<MessageInfo>, u64) {
@@ -67,32 +181,313 @@ impl MessagesInbox for StoredQueueMessagesInbox {
             "Polling messages"
         );
 
-        let mut inbox = self.inbox.get(&receiver).await
-            .unwrap_or_default();
+        self.take_messages(&receiver, &channel, limit).await
+    }
 
-        let mut messages = Vec::new();
+    async fn lease_messages(&self, receiver: PublicKey, channel: String, limit: Option<u64>, timeout: Duration) -> (Vec<(u64, MessageInfo)>, u64) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            receiver = receiver.to_base64(),
+            channel,
+            limit,
+            timeout = timeout.as_secs(),
+            "Leasing messages"
+        );
 
-        let mut limit = limit.unwrap_or(inbox.len() as u64);
-        let mut i = 0;
+        let Some(inbox) = self.inbox.get(&receiver).await else {
+            return (vec![], 0);
+        };
 
-        while inbox.len() > i && limit > 0 {
-            let record = &inbox[i];
+        let mut inbox = inbox.lock()
+            .expect("Failed to lock receiver's inbox");
 
-            if record.channel == channel {
-                limit -= 1;
+        let (messages, remained) = inbox.take_messages(&channel, limit);
 
-                messages.push(inbox.remove(i));
-            }
+        let leased_until = timestamp().saturating_add(timeout.as_secs());
 
-            else {
-                i += 1;
+        let messages = messages.into_iter()
+            .map(|info| LeasedMessage {
+                delivery_id: safe_random_u64(),
+                leased_until,
+                info
+            })
+            .collect::<Vec<_>>();
+
+        inbox.leased.extend_from_slice(&messages);
+
+        let messages = messages.into_iter()
+            .map(|message| (message.delivery_id, message.info))
+            .collect();
+
+        (messages, remained)
+    }
+
+    async fn ack_messages(&self, receiver: PublicKey, delivery_ids: Vec<u64>) -> u64 {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            receiver = receiver.to_base64(),
+            ?delivery_ids,
+            "Acknowledging messages"
+        );
+
+        let Some(inbox) = self.inbox.get(&receiver).await else {
+            return 0;
+        };
+
+        let mut inbox = inbox.lock()
+            .expect("Failed to lock receiver's inbox");
+
+        // Return expired messages to the inbox first
+        // so they can't be acknowledged
+        inbox.return_expired();
+
+        let stored = inbox.leased.len();
+
+        inbox.leased.retain(|message| !delivery_ids.contains(&message.delivery_id));
+
+        (stored - inbox.leased.len()) as u64
+    }
+
+    async fn remove_inbox(&self, receiver: PublicKey) -> Vec<MessageInfo> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(receiver = receiver.to_base64(), "Removing inbox");
+
+        let Some(inbox) = self.inbox.remove(&receiver).await else {
+            return vec![];
+        };
+
+        let mut inbox = inbox.lock()
+            .expect("Failed to lock receiver's inbox");
+
+        let mut messages = std::mem::take(&mut inbox.messages);
+
+        messages.extend(inbox.leased.drain(..).map(|message| message.info));
+
+        messages.sort_by_key(|info| info.received_at);
+
+        messages
+    }
+
+    #[inline]
+    fn subscribe(&self, receiver: &PublicKey, channel: &str, timeout: Duration) -> Option<MessagesNotification> {
+        Some(self.notifier.subscribe(receiver, channel, timeout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::rest_api::types::sender::tests::get_sender;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn limits() -> Result<(), Error> {
+        let inbox = StoredQueueMessagesInbox::new(Duration::from_secs(60), MessagesInboxLimits {
+            max_messages: Some(2),
+            max_total_size: None,
+            max_message_size: Some(16)
+        });
+
+        let receiver = SecretKey::random().public_key();
+        let encoding = MessageEncoding::from_str("base64").unwrap();
+
+        let message = Message::new("Hello", "World", encoding);
+        let large_message = Message::new("Hello, World!", "Hello, World!", encoding);
+
+        inbox.add_message(get_sender(), receiver.clone(), String::from("test"), message.clone()).await?;
+
+        assert!(matches!(
+            inbox.add_message(get_sender(), receiver.clone(), String::from("test"), large_message).await,
+            Err(Error::MessageTooLarge { size: 26, limit: 16 })
+        ));
+
+        inbox.add_message(get_sender(), receiver.clone(), String::from("test"), message.clone()).await?;
+
+        assert!(matches!(
+            inbox.add_message(get_sender(), receiver.clone(), String::from("test"), message.clone()).await,
+            Err(Error::InboxFull)
+        ));
+
+        // Polled messages free the inbox
+        let (messages, remaining) = inbox.poll_messages(receiver.clone(), String::from("test"), Some(1)).await;
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(remaining, 1);
+
+        inbox.add_message(get_sender(), receiver, String::from("test"), message).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn concurrent_limits() -> Result<(), Error> {
+        let inbox = StoredQueueMessagesInbox::new(Duration::from_secs(60), MessagesInboxLimits {
+            max_messages: Some(16),
+            max_total_size: None,
+            max_message_size: None
+        });
+
+        let receiver = SecretKey::random().public_key();
+        let encoding = MessageEncoding::from_str("base64").unwrap();
+
+        let tasks = (0..64)
+            .map(|_| {
+                let inbox = inbox.clone();
+                let receiver = receiver.clone();
+
+                tokio::spawn(async move {
+                    inbox.add_message(get_sender(), receiver, String::from("test"), Message::new("Hello", "World", encoding)).await
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut added = 0;
+
+        for task in tasks {
+            if task.await.unwrap().is_ok() {
+                added += 1;
             }
         }
 
-        let ramined = inbox.len() as u64;
+        // Every accepted message is stored, and no more than allowed
+        let (messages, remaining) = inbox.poll_messages(receiver, String::from("test"), None).await;
+
+        assert_eq!(added, 16);
+        assert_eq!(messages.len(), 16);
+        assert_eq!(remaining, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lease() -> Result<(), Error> {
+        let inbox = StoredQueueMessagesInbox::new(Duration::from_secs(60), MessagesInboxLimits::unlimited());
+
+        let receiver = SecretKey::random().public_key();
+        let encoding = MessageEncoding::from_str("base64").unwrap();
+
+        for _ in 0..3 {
+            inbox.add_message(get_sender(), receiver.clone(), String::from("test"), Message::new("Hello", "World", encoding)).await?;
+        }
+
+        // Leased messages are hidden from the inbox
+        let (messages, remaining) = inbox.lease_messages(receiver.clone(), String::from("test"), Some(2), Duration::from_secs(60)).await;
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(remaining, 1);
+
+        let (polled, remaining) = inbox.poll_messages(receiver.clone(), String::from("test"), None).await;
+
+        assert_eq!(polled.len(), 1);
+        assert_eq!(remaining, 0);
+
+        // Acknowledged messages are removed
+        assert_eq!(inbox.ack_messages(receiver.clone(), vec![messages[0].0, messages[1].0, 0]).await, 2);
+        assert_eq!(inbox.ack_messages(receiver.clone(), vec![messages[0].0]).await, 0);
+
+        // Messages with expired leases are returned to the inbox
+        inbox.add_message(get_sender(), receiver.clone(), String::from("test"), Message::new("Hello", "World", encoding)).await?;
+
+        let (messages, _) = inbox.lease_messages(receiver.clone(), String::from("test"), None, Duration::ZERO).await;
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(inbox.ack_messages(receiver.clone(), vec![messages[0].0]).await, 0);
+
+        let (polled, remaining) = inbox.poll_messages(receiver.clone(), String::from("test"), None).await;
+
+        assert_eq!(polled.len(), 1);
+        assert_eq!(remaining, 0);
+
+        // Too long leases don't overflow
+        inbox.add_message(get_sender(), receiver.clone(), String::from("test"), Message::new("Hello", "World", encoding)).await?;
+
+        let (messages, _) = inbox.lease_messages(receiver.clone(), String::from("test"), None, Duration::from_secs(u64::MAX)).await;
+
+        assert_eq!(inbox.ack_messages(receiver, vec![messages[0].0]).await, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn concurrent_lease() -> Result<(), Error> {
+        let inbox = StoredQueueMessagesInbox::new(Duration::from_secs(60), MessagesInboxLimits::unlimited());
+
+        let receiver = SecretKey::random().public_key();
+        let encoding = MessageEncoding::from_str("base64").unwrap();
+
+        for _ in 0..64 {
+            inbox.add_message(get_sender(), receiver.clone(), String::from("test"), Message::new("Hello", "World", encoding)).await?;
+        }
+
+        // Lease messages one by one concurrently,
+        // acknowledging only every second of them
+        let tasks = (0..64)
+            .map(|i| {
+                let inbox = inbox.clone();
+                let receiver = receiver.clone();
+
+                tokio::spawn(async move {
+                    let (messages, _) = inbox.lease_messages(receiver.clone(), String::from("test"), Some(1), Duration::from_secs(60)).await;
+
+                    if i % 2 == 0 {
+                        let delivery_ids = messages.iter()
+                            .map(|(delivery_id, _)| *delivery_id)
+                            .collect();
+
+                        inbox.ack_messages(receiver, delivery_ids).await
+                    } else {
+                        0
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut acknowledged = 0;
+
+        for task in tasks {
+            acknowledged += task.await.unwrap();
+        }
+
+        assert_eq!(acknowledged, 32);
+
+        // Every not acknowledged message is still leased
+        let inbox = inbox.inbox.get(&receiver).await.unwrap();
+        let inbox = inbox.lock().unwrap();
+
+        assert!(inbox.messages.is_empty());
+        assert_eq!(inbox.leased.len(), 32);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn subscribe() -> Result<(), Error> {
+        let inbox = StoredQueueMessagesInbox::default();
+
+        let receiver = SecretKey::random().public_key();
+        let encoding = MessageEncoding::from_str("base64").unwrap();
+
+        let notification = inbox.subscribe(&receiver, "test", Duration::from_secs(5)).unwrap();
+
+        tokio::spawn({
+            let inbox = inbox.clone();
+            let receiver = receiver.clone();
+
+            async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+
+                inbox.add_message(get_sender(), receiver, String::from("test"), Message::new("Hello", "World", encoding)).await
+            }
+        });
+
+        assert!(notification.await);
+
+        let (polled, _) = inbox.poll_messages(receiver, String::from("test"), None).await;
 
-        self.inbox.insert(receiver, inbox).await;
+        assert_eq!(polled.len(), 1);
 
-        (messages, ramined)
+        Ok(())
     }
 }
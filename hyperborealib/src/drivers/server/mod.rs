@@ -16,9 +16,13 @@ pub mod prelude {
         ServerParams
     };
 
-    pub use super::router::Router;
-    pub use super::traversal::Traversal;
-    pub use super::messages_inbox::MessagesInbox;
+    pub use super::router::{Router, RouterAvailability};
+    pub use super::traversal::{Traversal, TraversalReport};
+    pub use super::messages_inbox::{
+        MessagesInbox,
+        MessagesInboxLimits,
+        Error as MessagesInboxError
+    };
 
     #[cfg(feature = "router-global-table")]
     pub use super::router::global_table::GlobalTableRouter;
@@ -28,4 +32,7 @@ pub mod prelude {
 
     #[cfg(feature = "inbox-stored-queue")]
     pub use super::messages_inbox::stored_queue::StoredQueueMessagesInbox;
+
+    #[cfg(feature = "inbox-append-log")]
+    pub use super::messages_inbox::append_log::AppendLogMessagesInbox;
 }
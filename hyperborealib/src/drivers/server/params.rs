@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use crate::crypto::asymmetric::SecretKey;
+use crate::rest_api::types::ServerAddress;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ServerParams {
@@ -8,14 +11,97 @@ pub struct ServerParams {
     /// 
     /// This is needed when we perform requests
     /// from the server as a `server(addresss)` client.
-    pub address: String
+    pub address: ServerAddress,
+
+    /// Maximal difference between the server's time
+    /// and the timestamp of incoming requests.
+    /// 
+    /// Older requests are rejected to prevent
+    /// replay attacks.
+    pub request_lifetime: Duration,
+
+    /// Maximal number of processed requests remembered
+    /// to reject their duplicates.
+    /// 
+    /// When exceeded, new requests are rejected until
+    /// the remembered ones expire.
+    pub replay_guard_capacity: u64,
+
+    /// Minimal standard version of accepted requests.
+    /// 
+    /// First standard requests sign only the proof seed,
+    /// so their bodies can be swapped. They're rejected
+    /// unless this value is set to `1`.
+    pub min_standard: u64,
+
+    /// Forward messages sent to remote clients
+    /// to their servers found in the routing table.
+    /// 
+    /// When disabled, messages can be sent only
+    /// to the clients connected to this server.
+    pub forward_messages: bool,
+
+    /// Verify announced servers by requesting
+    /// their `GET /api/v1/info` and comparing
+    /// returned public keys with the announced ones.
+    /// 
+    /// When disabled, only signatures of announced
+    /// clients' certificates are verified.
+    pub probe_announced_servers: bool,
+
+    /// Maximal time for which `POST /api/v1/poll`
+    /// requests with the `wait` field are held
+    /// until messages arrive.
+    /// 
+    /// Longer timeouts requested by clients are
+    /// reduced to this value. Zero disables
+    /// long polling.
+    pub max_poll_wait: Duration,
+
+    /// Maximal time for which messages leased
+    /// by `POST /api/v1/poll` requests are hidden.
+    /// 
+    /// Longer leases requested by clients are
+    /// reduced to this value.
+    pub max_lease: Duration,
+
+    /// Maximal number of messages in a single
+    /// `POST /api/v1/send_batch` request.
+    /// 
+    /// Larger batches are rejected entirely.
+    pub max_batch_size: u64,
+
+    /// Maximal number of servers in a chain
+    /// requested by this server to resolve
+    /// recursive `POST /api/v1/lookup` requests.
+    /// 
+    /// Zero disables recursive lookups, so such
+    /// requests are answered with lookup hints.
+    /// Recursive lookups are disabled by default
+    /// since every such request makes this server
+    /// request other servers.
+    pub lookup_max_hops: u64,
+
+    /// Maximal time spent on a single
+    /// recursive lookup.
+    pub lookup_timeout: Duration
 }
 
 impl Default for ServerParams {
     fn default() -> Self {
         Self {
             secret_key: SecretKey::random(),
-            address: String::from("127.0.0.1:8001")
+            address: ServerAddress::new("http", "127.0.0.1", Some(8001), ""),
+            request_lifetime: Duration::from_secs(60 * 5),
+            replay_guard_capacity: 256 * 1024,
+            min_standard: 2,
+            forward_messages: false,
+            probe_announced_servers: false,
+            max_poll_wait: Duration::from_secs(30),
+            max_lease: Duration::from_secs(60 * 10),
+            max_batch_size: 128,
+            lookup_max_hops: 0,
+            lookup_timeout: Duration::from_secs(10)
         }
     }
 }
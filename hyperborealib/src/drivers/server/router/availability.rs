@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Thresholds used by the routers to derive clients' availability
+/// from the time they were last seen.
+/// 
+/// Local clients are seen when they connect to the server
+/// and every time they send a signed request to it. Remote
+/// clients are seen when they're announced to the server.
+/// 
+/// `None` eviction values mean that the clients are never evicted.
+pub struct RouterAvailability {
+    /// Local clients not seen for longer
+    /// than this are reported unavailable.
+    pub local_timeout: Duration,
+
+    /// Remote clients announced earlier than
+    /// this are reported unavailable.
+    pub remote_timeout: Duration,
+
+    /// Local clients not seen for longer than
+    /// this are removed from the routing table.
+    pub local_eviction: Option<Duration>,
+
+    /// Remote clients announced earlier than this
+    /// are removed from the routing table.
+    pub remote_eviction: Option<Duration>
+}
+
+impl Default for RouterAvailability {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            local_timeout: Duration::from_secs(60 * 5),
+            remote_timeout: Duration::from_secs(60 * 60),
+            local_eviction: Some(Duration::from_secs(60 * 60 * 24 * 7)),
+            remote_eviction: Some(Duration::from_secs(60 * 60 * 24))
+        }
+    }
+}
+
+impl RouterAvailability {
+    /// Check if the local client last seen
+    /// at the given timestamp is available.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// 
+    /// use hyperborealib::drivers::server::router::RouterAvailability;
+    /// 
+    /// let availability = RouterAvailability {
+    ///     local_timeout: Duration::from_secs(60),
+    ///     ..RouterAvailability::default()
+    /// };
+    /// 
+    /// assert!(availability.local_available(100, 150));
+    /// assert!(!availability.local_available(100, 200));
+    /// ```
+    pub fn local_available(&self, last_seen: u64, now: u64) -> bool {
+        Self::elapsed(last_seen, now) <= self.local_timeout
+    }
+
+    /// Check if the remote client announced
+    /// at the given timestamp is available.
+    pub fn remote_available(&self, announced_at: u64, now: u64) -> bool {
+        Self::elapsed(announced_at, now) <= self.remote_timeout
+    }
+
+    /// Check if the local client last seen at the
+    /// given timestamp should be evicted.
+    pub fn local_expired(&self, last_seen: u64, now: u64) -> bool {
+        self.local_eviction.is_some_and(|eviction| Self::elapsed(last_seen, now) > eviction)
+    }
+
+    /// Check if the remote client announced at the
+    /// given timestamp should be evicted.
+    pub fn remote_expired(&self, announced_at: u64, now: u64) -> bool {
+        self.remote_eviction.is_some_and(|eviction| Self::elapsed(announced_at, now) > eviction)
+    }
+
+    #[inline]
+    fn elapsed(since: u64, now: u64) -> Duration {
+        Duration::from_secs(now.saturating_sub(since))
+    }
+}
@@ -2,10 +2,11 @@ use std::path::PathBuf;
 
 use serde_json::{json, Value as Json};
 
+use crate::crypto::prelude::*;
 use crate::rest_api::prelude::*;
 use crate::time::timestamp;
 
-use super::Router;
+use super::{Router, RouterAvailability};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -19,12 +20,30 @@ pub enum Error {
     Serialize(#[from] serde_json::Error)
 }
 
+/// Last seen time of local clients is updated no more often
+/// than once per `local_timeout / LAST_SEEN_PRECISION_DIVISOR`.
+const LAST_SEEN_PRECISION_DIVISOR: u32 = 10;
+
 #[derive(Debug, Clone)]
 /// Global Table Router stores all the record in a separate
 /// files within the given folder.
+/// 
+/// Clients are indexed only if their connection certificates
+/// are signed by them, and stored records are replaced only
+/// by certificates with the same or newer `auth_date`.
+/// When client is indexed as local it is removed from the
+/// remote clients, and vice versa.
+/// 
+/// Last seen time of local clients is stored separately from
+/// their records, and remote clients are aged by the time they
+/// were announced. Clients' availability is derived from these
+/// timestamps, and idle clients are removed by the `sweep` method.
 pub struct GlobalTableRouter {
     /// Path to the routing table's folder.
-    pub storage_folder: PathBuf
+    pub storage_folder: PathBuf,
+
+    /// Thresholds of the clients' availability.
+    pub availability: RouterAvailability
 }
 
 impl GlobalTableRouter {
@@ -37,11 +56,226 @@ impl GlobalTableRouter {
         tokio::fs::create_dir_all(storage_folder.join("local")).await?;
         tokio::fs::create_dir_all(storage_folder.join("remote")).await?;
         tokio::fs::create_dir_all(storage_folder.join("servers")).await?;
+        tokio::fs::create_dir_all(storage_folder.join("last_seen")).await?;
 
         Ok(Self {
-            storage_folder
+            storage_folder,
+            availability: RouterAvailability::default()
         })
     }
+
+    #[inline]
+    /// Change thresholds of the clients' availability.
+    pub fn with_availability(mut self, availability: RouterAvailability) -> Self {
+        self.availability = availability;
+
+        self
+    }
+
+    /// Remove idle clients from the routing table.
+    /// 
+    /// Local clients are removed if they weren't seen, and
+    /// remote clients if they weren't announced for longer
+    /// than the eviction thresholds. This method should
+    /// be called periodically.
+    /// 
+    /// Return amount of removed clients. Records which
+    /// can't be read or removed are skipped, so one broken
+    /// record doesn't prevent eviction of the others.
+    pub async fn sweep(&self) -> Result<usize, Error> {
+        let now = timestamp();
+
+        let mut evicted = 0;
+
+        for table in ["local", "remote"] {
+            let mut entries = tokio::fs::read_dir(self.storage_folder.join(table)).await?;
+
+            while let Some(entry) = entries.next_entry().await? {
+                match self.sweep_record(table, entry.path(), now).await {
+                    Ok(true) => evicted += 1,
+                    Ok(false) => (),
+
+                    // Record could be removed by a concurrent request
+                    Err(Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => (),
+
+                    Err(_err) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(path = ?entry.path(), table, "Failed to sweep client record: {_err}");
+                    }
+                }
+            }
+        }
+
+        Ok(evicted)
+    }
+
+    /// Remove client's record from the given table if it's idle.
+    /// 
+    /// Return whether the record was removed.
+    async fn sweep_record(&self, table: &str, path: PathBuf, now: u64) -> Result<bool, Error> {
+        let record = tokio::fs::read(path).await?;
+        let record = serde_json::from_slice::<Json>(&record)?;
+
+        let client = Client::from_json(&record["client"])?;
+
+        let expired = if table == "local" {
+            let last_seen = self.last_seen(&client.public_key, &record).await;
+
+            self.availability.local_expired(last_seen, now)
+        } else {
+            let announced_at = record["indexed_at"].as_u64().unwrap_or_default();
+
+            self.availability.remote_expired(announced_at, now)
+        };
+
+        if expired {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(client = client.public_key.to_base64(), table, "Evicting idle client");
+
+            self.remove_client(table, &client.public_key).await?;
+        }
+
+        Ok(expired)
+    }
+
+    /// Read stored client's record from the given table.
+    async fn stored_record(&self, table: &str, client: &PublicKey) -> Result<Option<Json>, Error> {
+        let path = self.storage_folder
+            .join(table)
+            .join(client.to_base64());
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let record = tokio::fs::read(path).await?;
+
+        Ok(Some(serde_json::from_slice::<Json>(&record)?))
+    }
+
+    /// Get last seen timestamp of the local client.
+    /// 
+    /// Falls back to the record's indexing time if the
+    /// client wasn't seen since it was indexed.
+    async fn last_seen(&self, client: &PublicKey, record: &Json) -> u64 {
+        match self.stored_last_seen(client).await {
+            Some(last_seen) => last_seen,
+            None => record["indexed_at"].as_u64().unwrap_or_default()
+        }
+    }
+
+    /// Read stored last seen timestamp of the local client.
+    async fn stored_last_seen(&self, client: &PublicKey) -> Option<u64> {
+        let path = self.storage_folder
+            .join("last_seen")
+            .join(client.to_base64());
+
+        tokio::fs::read_to_string(path).await.ok()
+            .and_then(|last_seen| last_seen.trim().parse::<u64>().ok())
+    }
+
+    /// Store current timestamp as last seen time of the local client.
+    async fn update_last_seen(&self, client: &PublicKey) -> Result<(), Error> {
+        let path = self.storage_folder
+            .join("last_seen")
+            .join(client.to_base64());
+
+        tokio::fs::write(path, timestamp().to_string()).await?;
+
+        Ok(())
+    }
+
+    /// Read stored client's certificate from the given table.
+    async fn stored_certificate(&self, table: &str, client: &PublicKey) -> Result<Option<ConnectionCertificate>, Error> {
+        let Some(record) = self.stored_record(table, client).await? else {
+            return Ok(None);
+        };
+
+        let client = Client::from_json(&record["client"])?;
+
+        Ok(Some(client.certificate))
+    }
+
+    /// Check that the client's certificate can be indexed.
+    /// 
+    /// Certificate must be signed by the client, and it must be
+    /// not older than the stored certificates of the same client.
+    /// Certificates with the same auth date are accepted only
+    /// if they're issued for the same server.
+    async fn can_index(&self, client: &Client) -> Result<bool, Error> {
+        let server = &client.certificate.token.public_key;
+
+        if !client.certificate.validate(&client.public_key, server).unwrap_or(false) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(client = client.public_key.to_base64(), "Rejected client with invalid certificate");
+
+            return Ok(false);
+        }
+
+        for table in ["local", "remote"] {
+            if let Some(stored) = self.stored_certificate(table, &client.public_key).await? {
+                let token = &client.certificate.token;
+
+                let outdated = stored.token.auth_date > token.auth_date ||
+                    (stored.token.auth_date == token.auth_date && stored.token.public_key != token.public_key);
+
+                if outdated {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        client = client.public_key.to_base64(),
+                        stored_auth_date = stored.token.auth_date,
+                        auth_date = client.certificate.token.auth_date,
+                        "Rejected client with outdated certificate"
+                    );
+
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Count records of the given table without reading them.
+    async fn count_records(&self, table: &str) -> Result<u64, Error> {
+        let mut entries = tokio::fs::read_dir(self.storage_folder.join(table)).await?;
+
+        let mut count = 0;
+
+        while entries.next_entry().await?.is_some() {
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Remove client's record from the given table.
+    /// 
+    /// Return whether the record existed.
+    async fn remove_client(&self, table: &str, client: &PublicKey) -> Result<bool, Error> {
+        let path = self.storage_folder
+            .join(table)
+            .join(client.to_base64());
+
+        let exists = path.exists();
+
+        if exists {
+            tokio::fs::remove_file(path).await?;
+        }
+
+        // Last seen time is stored only for local clients
+        if table == "local" {
+            let path = self.storage_folder
+                .join("last_seen")
+                .join(client.to_base64());
+
+            if path.exists() {
+                tokio::fs::remove_file(path).await?;
+            }
+        }
+
+        Ok(exists)
+    }
 }
 
 #[async_trait::async_trait]
@@ -49,9 +283,17 @@ impl Router for GlobalTableRouter {
     type Error = Error;
 
     async fn index_local_client(&self, client: Client) -> Result<bool, Self::Error> {
+        if !self.can_index(&client).await? {
+            return Ok(false);
+        }
+
+        self.remove_client("remote", &client.public_key).await?;
+
+        let public_key = client.public_key.clone();
+
         let path = self.storage_folder
             .join("local")
-            .join(client.public_key.to_base64());
+            .join(public_key.to_base64());
 
         let client = json!({
             "indexed_at": timestamp(),
@@ -60,18 +302,56 @@ impl Router for GlobalTableRouter {
 
         tokio::fs::write(path, serde_json::to_vec(&client)?).await?;
 
+        self.update_last_seen(&public_key).await?;
+
+        Ok(true)
+    }
+
+    async fn touch_local_client(&self, public_key: &PublicKey) -> Result<bool, Self::Error> {
+        let path = self.storage_folder
+            .join("local")
+            .join(public_key.to_base64());
+
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        // Clients send many requests, so the stored time is updated
+        // only when it's older than a fraction of the availability timeout
+        let precision = (self.availability.local_timeout / LAST_SEEN_PRECISION_DIVISOR).as_secs();
+
+        let outdated = match self.stored_last_seen(public_key).await {
+            Some(last_seen) => timestamp().saturating_sub(last_seen) >= precision,
+            None => true
+        };
+
+        if outdated {
+            self.update_last_seen(public_key).await?;
+        }
+
         Ok(true)
     }
 
     async fn index_remote_client(&self, client: Client, server: Server) -> Result<bool, Self::Error> {
+        // Certificate must be addressed to the client's server
+        if client.certificate.token.public_key != server.public_key {
+            return Ok(false);
+        }
+
+        if !self.can_index(&client).await? {
+            return Ok(false);
+        }
+
+        self.remove_client("local", &client.public_key).await?;
+
         let path = self.storage_folder
             .join("remote")
             .join(client.public_key.to_base64());
 
         let record = json!({
             "indexed_at": timestamp(),
-            "client": client,
-            "server": server
+            "client": client.to_json()?,
+            "server": server.to_json()?
         });
 
         tokio::fs::write(path, serde_json::to_vec(&record)?).await?;
@@ -79,6 +359,16 @@ impl Router for GlobalTableRouter {
         Ok(true)
     }
 
+    #[inline]
+    async fn remove_local_client(&self, public_key: &PublicKey) -> Result<bool, Self::Error> {
+        self.remove_client("local", public_key).await
+    }
+
+    #[inline]
+    async fn remove_remote_client(&self, public_key: &PublicKey) -> Result<bool, Self::Error> {
+        self.remove_client("remote", public_key).await
+    }
+
     async fn index_server(&self, server: Server) -> Result<bool, Self::Error> {
         let path = self.storage_folder
             .join("servers")
@@ -154,13 +444,65 @@ impl Router for GlobalTableRouter {
 
         Ok(servers)
     }
+
+    #[inline]
+    async fn local_clients_count(&self) -> Result<u64, Self::Error> {
+        self.count_records("local").await
+    }
+
+    #[inline]
+    async fn remote_clients_count(&self) -> Result<u64, Self::Error> {
+        self.count_records("remote").await
+    }
+
+    #[inline]
+    async fn servers_count(&self) -> Result<u64, Self::Error> {
+        self.count_records("servers").await
+    }
+
+    async fn lookup_local_client(&self, public_key: &PublicKey, client_type: Option<ClientType>) -> Result<Option<(Client, bool)>, Self::Error> {
+        let Some(record) = self.stored_record("local", public_key).await? else {
+            return Ok(None);
+        };
+
+        let client = Client::from_json(&record["client"])?;
+
+        if client_type.is_some() && client_type != Some(client.info.client_type) {
+            return Ok(None);
+        }
+
+        let last_seen = self.last_seen(public_key, &record).await;
+        let available = self.availability.local_available(last_seen, timestamp());
+
+        Ok(Some((client, available)))
+    }
+
+    async fn lookup_remote_client(&self, public_key: &PublicKey, client_type: Option<ClientType>) -> Result<Option<(Client, Server, bool)>, Self::Error> {
+        let Some(record) = self.stored_record("remote", public_key).await? else {
+            return Ok(None);
+        };
+
+        let client = Client::from_json(&record["client"])?;
+        let server = Server::from_json(&record["server"])?;
+
+        if client_type.is_some() && client_type != Some(client.info.client_type) {
+            return Ok(None);
+        }
+
+        let announced_at = record["indexed_at"].as_u64().unwrap_or_default();
+        let available = self.availability.remote_available(announced_at, timestamp());
+
+        Ok(Some((client, server, available)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::rest_api::types::client::tests::get_client;
+    use crate::rest_api::types::client::tests::{get_client, get_client_for};
     use crate::rest_api::types::server::tests::get_server;
 
+    use std::time::Duration;
+
     use super::*;
 
     #[tokio::test]
@@ -177,7 +519,9 @@ mod tests {
         let table = GlobalTableRouter::new(&temp).await?;
 
         let local = vec![get_client(); 32];
-        let remote = vec![(get_client(), get_server()); 32];
+        let server = get_server();
+
+        let remote = vec![(get_client_for(server.public_key.clone()), server); 32];
         let servers = vec![get_server(); 32];
 
         // Index clients
@@ -230,6 +574,151 @@ mod tests {
             assert_eq!(server, found.0);
         }
 
+        // Count records
+
+        assert_eq!(table.local_clients_count().await.unwrap(), table.local_clients().await.unwrap().len() as u64);
+        assert_eq!(table.remote_clients_count().await.unwrap(), table.remote_clients().await.unwrap().len() as u64);
+        assert_eq!(table.servers_count().await.unwrap(), table.servers().await.unwrap().len() as u64);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn auth_date() -> Result<(), Error> {
+        let temp = std::env::temp_dir()
+            .join("global-table-router-auth-date-test");
+
+        if temp.exists() {
+            std::fs::remove_dir_all(&temp)?;
+        }
+
+        let table = GlobalTableRouter::new(&temp).await?;
+
+        let client_secret = SecretKey::random();
+        let server = get_server();
+
+        let client = |auth_date: u64, server: &Server| {
+            let token = ConnectionToken::new(auth_date, server.public_key.clone());
+
+            let certificate = ConnectionCertificate {
+                sign: client_secret.create_signature(token.to_bytes()),
+                token
+            };
+
+            Client::new(client_secret.public_key(), certificate, ClientInfo::thin())
+        };
+
+        // Certificate is not signed by the client
+        let mut invalid = client(100, &server);
+
+        invalid.public_key = SecretKey::random().public_key();
+
+        assert!(!table.index_local_client(invalid).await?);
+
+        // Certificate is not addressed to the client's server
+        assert!(!table.index_remote_client(client(100, &server), get_server()).await?);
+
+        // Newer certificates replace older ones
+        assert!(table.index_local_client(client(100, &server)).await?);
+        assert!(table.index_local_client(client(100, &server)).await?);
+
+        let remote_server = get_server();
+
+        assert!(table.index_remote_client(client(200, &remote_server), remote_server.clone()).await?);
+
+        assert!(table.lookup_local_client(&client_secret.public_key(), None).await?.is_none());
+
+        // Older certificates are rejected
+        assert!(!table.index_local_client(client(150, &server)).await?);
+        assert!(!table.index_remote_client(client(150, &server), server.clone()).await?);
+
+        // Certificates of the same date are rejected unless
+        // they're issued for the same server
+        assert!(!table.index_local_client(client(200, &server)).await?);
+        assert!(!table.index_remote_client(client(200, &server), server.clone()).await?);
+        assert!(table.index_remote_client(client(200, &remote_server), remote_server.clone()).await?);
+
+        let (_, found, _) = table.lookup_remote_client(&client_secret.public_key(), None).await?.unwrap();
+
+        assert_eq!(found, remote_server);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn availability() -> Result<(), Error> {
+        let temp = std::env::temp_dir()
+            .join("global-table-router-availability-test");
+
+        if temp.exists() {
+            std::fs::remove_dir_all(&temp)?;
+        }
+
+        let table = GlobalTableRouter::new(&temp).await?
+            .with_availability(RouterAvailability {
+                local_timeout: Duration::from_secs(60),
+                remote_timeout: Duration::from_secs(60),
+                local_eviction: Some(Duration::from_secs(120)),
+                remote_eviction: None
+            });
+
+        let local = get_client();
+        let server = get_server();
+        let remote = get_client_for(server.public_key.clone());
+
+        assert!(table.index_local_client(local.clone()).await?);
+        assert!(table.index_remote_client(remote.clone(), server).await?);
+
+        // Freshly indexed clients are available
+        assert!(table.lookup_local_client(&local.public_key, None).await?.unwrap().1);
+        assert!(table.lookup_remote_client(&remote.public_key, None).await?.unwrap().2);
+
+        // Only local clients can be touched
+        assert!(table.touch_local_client(&local.public_key).await?);
+        assert!(!table.touch_local_client(&remote.public_key).await?);
+
+        // Age the records
+        let last_seen = temp.join("last_seen").join(local.public_key.to_base64());
+
+        std::fs::write(last_seen, (timestamp() - 90).to_string())?;
+
+        let path = temp.join("remote").join(remote.public_key.to_base64());
+
+        let mut record = serde_json::from_slice::<Json>(&std::fs::read(&path)?)?;
+
+        record["indexed_at"] = json!(timestamp() - 1000);
+
+        std::fs::write(&path, serde_json::to_vec(&record)?)?;
+
+        assert!(!table.lookup_local_client(&local.public_key, None).await?.unwrap().1);
+        assert!(!table.lookup_remote_client(&remote.public_key, None).await?.unwrap().2);
+
+        // Touched client becomes available again
+        assert!(table.touch_local_client(&local.public_key).await?);
+        assert!(table.lookup_local_client(&local.public_key, None).await?.unwrap().1);
+
+        // Recently seen time is not rewritten
+        let last_seen = temp.join("last_seen").join(local.public_key.to_base64());
+
+        let recently = (timestamp() - 3).to_string();
+
+        std::fs::write(&last_seen, &recently)?;
+
+        assert!(table.touch_local_client(&local.public_key).await?);
+
+        assert_eq!(std::fs::read_to_string(&last_seen)?, recently);
+
+        // Idle local client is evicted, remote clients are kept,
+        // and broken records don't stop the eviction
+        std::fs::write(last_seen, (timestamp() - 200).to_string())?;
+
+        std::fs::write(temp.join("local").join("broken"), "broken record")?;
+
+        assert_eq!(table.sweep().await?, 1);
+
+        assert!(table.lookup_local_client(&local.public_key, None).await?.is_none());
+        assert!(table.lookup_remote_client(&remote.public_key, None).await?.is_some());
+
         Ok(())
     }
 }
@@ -4,6 +4,10 @@ use crate::rest_api::prelude::*;
 #[cfg(feature = "router-global-table")]
 pub mod global_table;
 
+mod availability;
+
+pub use availability::RouterAvailability;
+
 #[async_trait::async_trait]
 /// Router is a struct that implements network clients
 /// and servers indexing, listing and lookup operations.
@@ -13,15 +17,45 @@ pub trait Router {
     /// Index local client in the routing table.
     /// 
     /// This method will return whether the client was indexed.
+    /// Router should refuse clients with invalid certificates,
+    /// and clients which certificates are older (have lower
+    /// `auth_date`) than the already indexed ones.
     async fn index_local_client(&self, client: Client) -> Result<bool, Self::Error>;
 
     /// Index remote client in the routing table.
     /// 
     /// This method will return whether the client was indexed.
+    /// Router should refuse clients with invalid certificates,
+    /// and clients which certificates are older (have lower
+    /// `auth_date`) than the already indexed ones.
     async fn index_remote_client(&self, client: Client, _server: Server) -> Result<bool, Self::Error> {
         self.index_local_client(client).await
     }
 
+    /// Update last seen time of the local client.
+    /// 
+    /// This method is called every time the client sends
+    /// a signed request to the server, and returns whether
+    /// the client is indexed as local. Routers can use it
+    /// to derive clients' availability.
+    async fn touch_local_client(&self, _public_key: &PublicKey) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    /// Remove local client from the routing table.
+    /// 
+    /// This method will return whether the client was removed.
+    async fn remove_local_client(&self, _public_key: &PublicKey) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    /// Remove remote client from the routing table.
+    /// 
+    /// This method will return whether the client was removed.
+    async fn remove_remote_client(&self, _public_key: &PublicKey) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
     /// Index server in the routing table.
     /// 
     /// This method will return whether the server was indexed.
@@ -42,6 +76,30 @@ pub trait Router {
             .collect::<Vec<_>>())
     }
 
+    /// Get amount of connected local clients.
+    /// 
+    /// Routers should implement it without
+    /// reading the clients' records.
+    async fn local_clients_count(&self) -> Result<u64, Self::Error> {
+        Ok(self.local_clients().await?.len() as u64)
+    }
+
+    /// Get amount of known remote clients.
+    /// 
+    /// Routers should implement it without
+    /// reading the clients' records.
+    async fn remote_clients_count(&self) -> Result<u64, Self::Error> {
+        Ok(self.remote_clients().await?.len() as u64)
+    }
+
+    /// Get amount of known servers.
+    /// 
+    /// Routers should implement it without
+    /// reading the servers' records.
+    async fn servers_count(&self) -> Result<u64, Self::Error> {
+        Ok(self.servers().await?.len() as u64)
+    }
+
     /// Lookup local client in the routing table.
     /// 
     /// Router can return optional availability field.
@@ -1,35 +1,413 @@
-use std::collections::VecDeque;
+use std::collections::HashSet;
+use std::time::Duration;
+
+use futures::StreamExt;
 
 use crate::http::client::HttpClient;
 use crate::rest_api::middleware::Client as ClientMiddleware;
 
 use super::*;
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct BfsRecursionTraversal;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Breadth-first search network traversal.
+/// 
+/// Requests `GET /api/v1/servers` of all the known servers,
+/// then of the servers returned by them and so on. Every
+/// server is requested only once per traversal. Returned
+/// servers which are already known are indexed again
+/// to update their addresses.
+/// 
+/// Optionally requests `GET /api/v1/clients` of the visited
+/// servers and indexes their clients as remote ones. Clients
+/// with invalid certificates are skipped, and outdated
+/// certificates are refused by the router.
+pub struct BfsRecursionTraversal {
+    /// Maximal distance (in hops) from the already known
+    /// servers to the requested ones. Known servers have
+    /// depth 0. If `None`, then depth is not limited.
+    pub max_depth: Option<u64>,
+
+    /// Maximal amount of requested servers.
+    /// If `None`, then it is not limited.
+    pub max_nodes: Option<u64>,
+
+    /// Maximal amount of simultaneous requests.
+    pub concurrency: usize,
+
+    /// Timeout of a single request.
+    pub request_timeout: Duration,
+
+    /// Index clients of the visited servers.
+    pub harvest_clients: bool
+}
+
+impl Default for BfsRecursionTraversal {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_depth: Some(8),
+            max_nodes: Some(1024),
+            concurrency: 16,
+            request_timeout: Duration::from_secs(10),
+            harvest_clients: false
+        }
+    }
+}
 
 #[async_trait::async_trait]
 impl Traversal for BfsRecursionTraversal {
-    async fn traverse<R, T, I>(&self, http_client: impl HttpClient, server: &ServerDriver<R, T, I>)
+    async fn traverse<R, T, I>(&self, http_client: impl HttpClient, server: &ServerDriver<R, T, I>) -> TraversalReport
     where
         R: Router + Sync,
         T: Traversal + Sync,
         I: MessagesInbox + Sync
     {
-        if let Ok(remote_servers) = server.router().servers().await {
-            let client = ClientMiddleware::new(http_client, server.as_client());
+        let mut report = TraversalReport::default();
+
+        let mut remote_servers = match server.router().servers().await {
+            Ok(remote_servers) => remote_servers,
+
+            Err(err) => {
+                report.errors.push(format!("Failed to read known servers: {err}"));
+
+                return report;
+            }
+        };
+
+        let client = ClientMiddleware::new(http_client, server.as_client());
+
+        // Don't request the current server and already known servers
+        let mut visited = remote_servers.iter()
+            .map(|remote_server| remote_server.public_key.clone())
+            .collect::<HashSet<_>>();
+
+        visited.insert(server.params().secret_key.public_key());
+
+        // Servers indexed during this traversal
+        let mut indexed = HashSet::from([
+            server.params().secret_key.public_key()
+        ]);
+
+        remote_servers.retain(|remote_server| remote_server.public_key != server.params().secret_key.public_key());
+
+        let mut depth = 0;
+        let mut requested = 0;
+
+        while !remote_servers.is_empty() {
+            if self.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                break;
+            }
+
+            if let Some(max_nodes) = self.max_nodes {
+                let available = max_nodes.saturating_sub(requested);
+
+                if available == 0 {
+                    break;
+                }
+
+                remote_servers.truncate(available as usize);
+            }
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(depth, servers = remote_servers.len(), "Requesting servers");
+
+            requested += remote_servers.len() as u64;
 
-            let mut remote_servers = VecDeque::from(remote_servers);
+            let responses = futures::stream::iter(remote_servers)
+                .map(|remote_server| async {
+                    let servers = tokio::time::timeout(
+                        self.request_timeout,
+                        client.get_servers(&remote_server.address)
+                    );
 
-            while let Some(remote_server) = remote_servers.pop_front() {
-                if let Ok(mut response) = client.get_servers(&remote_server.address).await {
-                    for remote_server in response.drain(..) {
-                        remote_servers.push_back(remote_server);
+                    let clients = async {
+                        if !self.harvest_clients {
+                            return None;
+                        }
+
+                        let clients = tokio::time::timeout(
+                            self.request_timeout,
+                            client.get_clients(&remote_server.address)
+                        ).await;
+
+                        Some(clients)
+                    };
+
+                    let (servers, clients) = futures::join!(servers, clients);
+
+                    (remote_server, servers, clients)
+                })
+                .buffer_unordered(self.concurrency.max(1))
+                .collect::<Vec<_>>()
+                .await;
+
+            let mut next_servers = Vec::new();
+
+            for (remote_server, servers, clients) in responses {
+                match clients {
+                    Some(Ok(Ok(clients))) => {
+                        for remote_client in clients {
+                            // Skip clients which are not connected to this server
+                            if !remote_client.certificate.validate(&remote_client.public_key, &remote_server.public_key).unwrap_or(false) {
+                                report.errors.push(format!(
+                                    "Server {} returned client {} with invalid certificate",
+                                    remote_server.address,
+                                    remote_client.public_key.to_base64()
+                                ));
+
+                                continue;
+                            }
+
+                            // Router refuses certificates older than the indexed ones
+                            match server.router().index_remote_client(remote_client.clone(), remote_server.clone()).await {
+                                Ok(true) => report.clients.push((remote_client, remote_server.clone())),
+                                Ok(false) => (),
+
+                                Err(err) => report.errors.push(format!(
+                                    "Failed to index client {}: {err}",
+                                    remote_client.public_key.to_base64()
+                                ))
+                            }
+                        }
                     }
+
+                    Some(Ok(Err(err))) => report.errors.push(format!("Failed to request clients of server {}: {err}", remote_server.address)),
+                    Some(Err(_)) => report.errors.push(format!("Server {} clients request timed out", remote_server.address)),
+
+                    None => ()
                 }
 
-                let _ = server.router().index_server(remote_server).await;
+                let found_servers = match servers {
+                    Ok(Ok(found_servers)) => found_servers,
+
+                    Ok(Err(err)) => {
+                        report.errors.push(format!("Failed to request server {}: {err}", remote_server.address));
+                        report.unreachable.push(remote_server);
+
+                        continue;
+                    }
+
+                    Err(_) => {
+                        report.errors.push(format!("Server {} request timed out", remote_server.address));
+                        report.unreachable.push(remote_server);
+
+                        continue;
+                    }
+                };
+
+                for found_server in found_servers {
+                    if !indexed.insert(found_server.public_key.clone()) {
+                        continue;
+                    }
+
+                    // Known servers are indexed again but not requested
+                    let new_server = visited.insert(found_server.public_key.clone());
+
+                    match server.router().index_server(found_server.clone()).await {
+                        Ok(true) if new_server => {
+                            report.found.push(found_server.clone());
+
+                            next_servers.push(found_server);
+                        }
+
+                        Ok(_) => (),
+
+                        Err(err) => report.errors.push(format!("Failed to index server {}: {err}", found_server.address))
+                    }
+                }
             }
+
+            remote_servers = next_servers;
+            depth += 1;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            found = report.found.len(),
+            clients = report.clients.len(),
+            unreachable = report.unreachable.len(),
+            errors = report.errors.len(),
+            "Network traversal finished"
+        );
+
+        report
+    }
+}
+
+#[cfg(all(test, feature = "router-global-table", feature = "inbox-stored-queue"))]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::Value as Json;
+
+    use crate::crypto::prelude::*;
+    use crate::http::client::Response;
+    use crate::rest_api::types::client::tests::get_client_for;
+
+    use super::*;
+
+    #[derive(Debug, Clone, Default)]
+    /// HTTP client serving `GET` requests
+    /// from the given network map.
+    struct NetworkHttpClient {
+        network: Arc<HashMap<String, Json>>,
+        requests: Arc<Mutex<Vec<String>>>
+    }
+
+    #[async_trait::async_trait]
+    impl HttpClient for NetworkHttpClient {
+        async fn get(&self, url: impl AsRef<str> + Send) -> Result<Response, Box<dyn std::error::Error + Send + Sync>> {
+            let url = url.as_ref().to_string();
+
+            self.requests.lock().unwrap().push(url.clone());
+
+            let Some(body) = self.network.get(&url) else {
+                return Err("Server is unreachable".into());
+            };
+
+            Ok(Response {
+                status: 200,
+                body: Some(body.clone())
+            })
         }
+
+        async fn post(&self, _url: impl AsRef<str> + Send, _body: Json) -> Result<Response, Box<dyn std::error::Error + Send + Sync>> {
+            Err("Not supported".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn traverse() -> Result<(), Box<dyn std::error::Error>> {
+        let temp = std::env::temp_dir()
+            .join("bfs-recursion-traversal-test");
+
+        if temp.exists() {
+            std::fs::remove_dir_all(&temp)?;
+        }
+
+        let params = ServerParams::default();
+
+        let current = Server::new(params.secret_key.public_key(), params.address.clone());
+
+        let servers = (0..4)
+            .map(|i| Server::new(SecretKey::random().public_key(), format!("server-{i}").parse().unwrap()))
+            .collect::<Vec<_>>();
+
+        // Server 0 with changed address
+        let moved = Server::new(servers[0].public_key.clone(), "server-0-moved".parse().unwrap());
+
+        // 0 -> 1 -> 2 -> 0, 2 -> 3 (unreachable)
+        let network = HashMap::from([
+            (String::from("http://server-0/api/v1/servers"), ServersResponse::new([servers[1].clone(), current.clone()]).to_json()?),
+            (String::from("http://server-1/api/v1/servers"), ServersResponse::new([servers[2].clone()]).to_json()?),
+            (String::from("http://server-2/api/v1/servers"), ServersResponse::new([moved.clone(), servers[3].clone()]).to_json()?)
+        ]);
+
+        let http_client = NetworkHttpClient {
+            network: Arc::new(network),
+            requests: Arc::new(Mutex::new(Vec::new()))
+        };
+
+        let driver = ServerDriver::new(
+            GlobalTableRouter::new(&temp).await?,
+            BfsRecursionTraversal::default(),
+            StoredQueueMessagesInbox::default(),
+            params.clone()
+        );
+
+        driver.router().index_server(servers[0].clone()).await?;
+
+        // Every server is requested only once
+        let report = driver.traversal().traverse(http_client.clone(), &driver).await;
+
+        assert_eq!(report.found, &servers[1..]);
+        assert_eq!(report.unreachable, &servers[3..]);
+        assert_eq!(report.errors.len(), 1);
+
+        assert_eq!(http_client.requests.lock().unwrap().len(), 4);
+
+        // Address of the known server is updated
+        let (found, _) = driver.router().lookup_server(&servers[0].public_key).await?.unwrap();
+
+        assert_eq!(found, moved);
+
+        // Depth limit
+        tokio::fs::remove_dir_all(&temp).await?;
+
+        let driver = ServerDriver::new(
+            GlobalTableRouter::new(&temp).await?,
+            BfsRecursionTraversal {
+                max_depth: Some(1),
+                ..BfsRecursionTraversal::default()
+            },
+            StoredQueueMessagesInbox::default(),
+            params
+        );
+
+        driver.router().index_server(servers[0].clone()).await?;
+
+        let report = driver.traversal().traverse(http_client, &driver).await;
+
+        assert_eq!(report.found, &servers[1..2]);
+
+        tokio::fs::remove_dir_all(temp).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn harvest_clients() -> Result<(), Box<dyn std::error::Error>> {
+        let temp = std::env::temp_dir()
+            .join("bfs-recursion-traversal-clients-test");
+
+        if temp.exists() {
+            std::fs::remove_dir_all(&temp)?;
+        }
+
+        let server = Server::new(SecretKey::random().public_key(), "server-0".parse().unwrap());
+
+        let client = get_client_for(server.public_key.clone());
+        let spoofed = get_client_for(SecretKey::random().public_key());
+
+        let network = HashMap::from([
+            (String::from("http://server-0/api/v1/servers"), ServersResponse::new([]).to_json()?),
+            (String::from("http://server-0/api/v1/clients"), ClientsResponse::new([client.clone(), spoofed.clone()]).to_json()?)
+        ]);
+
+        let http_client = NetworkHttpClient {
+            network: Arc::new(network),
+            requests: Arc::new(Mutex::new(Vec::new()))
+        };
+
+        let driver = ServerDriver::new(
+            GlobalTableRouter::new(&temp).await?,
+            BfsRecursionTraversal {
+                harvest_clients: true,
+                ..BfsRecursionTraversal::default()
+            },
+            StoredQueueMessagesInbox::default(),
+            ServerParams::default()
+        );
+
+        driver.router().index_server(server.clone()).await?;
+
+        let report = driver.traversal().traverse(http_client, &driver).await;
+
+        assert_eq!(report.clients, [(client.clone(), server.clone())]);
+        assert_eq!(report.errors.len(), 1);
+
+        let (found_client, found_server, _) = driver.router()
+            .lookup_remote_client(&client.public_key, None).await?
+            .unwrap();
+
+        assert_eq!(found_client, client);
+        assert_eq!(found_server, server);
+
+        assert!(driver.router().lookup_remote_client(&spoofed.public_key, None).await?.is_none());
+
+        tokio::fs::remove_dir_all(temp).await?;
+
+        Ok(())
     }
 }
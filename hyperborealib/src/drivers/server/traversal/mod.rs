@@ -1,17 +1,34 @@
 use crate::http::client::HttpClient;
+use crate::rest_api::prelude::*;
 
 use super::prelude::*;
 
 #[cfg(feature = "traversal-bfs-recursion")]
 pub mod bfs_recursion;
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// Result of the network traversal.
+pub struct TraversalReport {
+    /// Newly found and indexed servers.
+    pub found: Vec<Server>,
+
+    /// Indexed remote clients with their servers.
+    pub clients: Vec<(Client, Server)>,
+
+    /// Servers which couldn't be requested.
+    pub unreachable: Vec<Server>,
+
+    /// Errors happened during the traversal.
+    pub errors: Vec<String>
+}
+
 #[async_trait::async_trait]
 /// Traversal is a struct that implements network servers
 /// searching. It is called manually by the dev and intended
 /// to keep the updated state of the network servers.
 pub trait Traversal {
     /// Update network map using given server.
-    async fn traverse<R, T, I>(&self, http_client: impl HttpClient, server: &ServerDriver<R, T, I>)
+    async fn traverse<R, T, I>(&self, http_client: impl HttpClient, server: &ServerDriver<R, T, I>) -> TraversalReport
     where
         R: Router + Sync,
         T: Traversal + Sync,
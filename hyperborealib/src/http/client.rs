@@ -2,6 +2,11 @@ use serde_json::Value as Json;
 
 use crate::rest_api::AsJson;
 
+#[cfg(all(unix, feature = "client-unix"))]
+use crate::rest_api::types::ServerAddress;
+
+use super::websocket::BoxedWebSocket;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Response {
     pub status: u16,
@@ -16,6 +21,20 @@ pub trait HttpClient: Clone + Send + Sync {
     /// Send HTTP POST request with JSON body
     async fn post(&self, url: impl AsRef<str> + Send, body: Json) -> Result<Response, Box<dyn std::error::Error + Send + Sync>>;
 
+    /// Open WebSocket connection
+    /// 
+    /// `url` has the same format as for other requests
+    /// (e.g. `https://example.org/api/v1/subscribe`), so
+    /// implementations should change the scheme if needed.
+    /// 
+    /// Default implementation returns an error for the
+    /// clients which don't support WebSockets.
+    async fn websocket(&self, url: impl AsRef<str> + Send) -> Result<BoxedWebSocket, Box<dyn std::error::Error + Send + Sync>> {
+        let _ = url;
+
+        Err("WebSockets are not supported by this HTTP client".into())
+    }
+
     /// Perform GET REST API request
     async fn get_request<T: AsJson>(&self, url: impl AsRef<str> + Send) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
         #[cfg(feature = "tracing")]
@@ -77,13 +96,51 @@ pub trait HttpClient: Clone + Send + Sync {
 
 #[cfg(feature = "client-reqwest")]
 #[derive(Debug, Clone)]
-pub struct ReqwestHttpClient(reqwest::Client);
+pub struct ReqwestHttpClient {
+    client: reqwest::Client,
+
+    /// PEM encoded CA certificate trusted by WebSocket connections.
+    #[cfg(feature = "client-reqwest-ws")]
+    ca_certificate: Option<Vec<u8>>
+}
 
 #[cfg(feature = "client-reqwest")]
 impl Default for ReqwestHttpClient {
     #[inline]
     fn default() -> Self {
-        Self(reqwest::Client::new())
+        Self::new(reqwest::Client::new())
+    }
+}
+
+#[cfg(feature = "client-reqwest")]
+impl ReqwestHttpClient {
+    #[inline]
+    /// Wrap already configured `reqwest` client.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self {
+            client,
+
+            #[cfg(feature = "client-reqwest-ws")]
+            ca_certificate: None
+        }
+    }
+
+    /// Create HTTP client which additionally trusts
+    /// the given PEM encoded CA certificate.
+    /// 
+    /// This is needed to connect to the servers
+    /// which use self-signed TLS certificates.
+    pub fn with_ca_certificate(certificate: impl AsRef<[u8]>) -> Result<Self, reqwest::Error> {
+        let client = reqwest::Client::builder()
+            .add_root_certificate(reqwest::Certificate::from_pem(certificate.as_ref())?)
+            .build()?;
+
+        Ok(Self {
+            client,
+
+            #[cfg(feature = "client-reqwest-ws")]
+            ca_certificate: Some(certificate.as_ref().to_vec())
+        })
     }
 }
 
@@ -91,7 +148,7 @@ impl Default for ReqwestHttpClient {
 #[async_trait::async_trait]
 impl HttpClient for ReqwestHttpClient {
     async fn get(&self, url: impl AsRef<str> + Send) -> Result<Response, Box<dyn std::error::Error + Send + Sync>> {
-        let response = self.0.get(url.as_ref())
+        let response = self.client.get(url.as_ref())
             .send().await
             .map_err(Box::new)?;
 
@@ -107,7 +164,7 @@ impl HttpClient for ReqwestHttpClient {
     }
 
     async fn post(&self, url: impl AsRef<str> + Send, body: Json) -> Result<Response, Box<dyn std::error::Error + Send + Sync>> {
-        let response = self.0.post(url.as_ref())
+        let response = self.client.post(url.as_ref())
             .json(&body)
             .send().await
             .map_err(Box::new)?;
@@ -122,4 +179,227 @@ impl HttpClient for ReqwestHttpClient {
             body: Some(body)
         })
     }
+
+    #[cfg(feature = "client-reqwest-ws")]
+    async fn websocket(&self, url: impl AsRef<str> + Send) -> Result<BoxedWebSocket, Box<dyn std::error::Error + Send + Sync>> {
+        use tokio_tungstenite::Connector;
+
+        use super::websocket::TungsteniteWebSocket;
+
+        let url = url.as_ref();
+
+        let url = if let Some(url) = url.strip_prefix("https://") {
+            format!("wss://{url}")
+        } else if let Some(url) = url.strip_prefix("http://") {
+            format!("ws://{url}")
+        } else {
+            url.to_string()
+        };
+
+        let connector = match &self.ca_certificate {
+            Some(certificate) => {
+                let mut roots = rustls::RootCertStore::empty();
+
+                roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+                for certificate in rustls_pemfile::certs(&mut certificate.as_slice()) {
+                    roots.add(certificate?)?;
+                }
+
+                let config = rustls::ClientConfig::builder()
+                    .with_root_certificates(roots)
+                    .with_no_client_auth();
+
+                Some(Connector::Rustls(std::sync::Arc::new(config)))
+            }
+
+            None => None
+        };
+
+        let (socket, _) = tokio_tungstenite::connect_async_tls_with_config(url, None, false, connector).await?;
+
+        Ok(Box::new(TungsteniteWebSocket(socket)))
+    }
+}
+
+#[cfg(all(unix, feature = "client-unix"))]
+#[derive(Default, Debug, Clone)]
+/// HTTP client which performs requests to the `unix://`
+/// addresses through the Unix domain sockets, and passes
+/// all the other requests to the inner HTTP client.
+/// 
+/// Unix socket URLs must be built by the `ServerAddress::endpoint`
+/// method, e.g. `unix://%2Frun%2Fhyperborea.sock/api/v1/info`.
+/// 
+/// ```rust
+/// use hyperborealib::http::{ReqwestHttpClient, UnixHttpClient};
+/// 
+/// let client = UnixHttpClient::new(ReqwestHttpClient::default());
+/// ```
+pub struct UnixHttpClient<T>(T);
+
+#[cfg(all(unix, feature = "client-unix"))]
+impl<T: HttpClient> UnixHttpClient<T> {
+    #[inline]
+    pub fn new(client: T) -> Self {
+        Self(client)
+    }
+
+    #[inline]
+    /// Get HTTP client used for non-unix addresses.
+    pub fn inner(&self) -> &T {
+        &self.0
+    }
+
+    /// Perform HTTP request through the given Unix domain socket.
+    async fn request(socket: String, request: hyper::Request<http_body_util::Full<hyper::body::Bytes>>) -> Result<Response, Box<dyn std::error::Error + Send + Sync>> {
+        use http_body_util::BodyExt;
+
+        let stream = tokio::net::UnixStream::connect(socket).await?;
+
+        let (mut sender, connection) = hyper::client::conn::http1::handshake(
+            hyper_util::rt::TokioIo::new(stream)
+        ).await?;
+
+        tokio::spawn(async move {
+            if let Err(_err) = connection.await {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(err = ?_err, "Unix socket connection error");
+            }
+        });
+
+        let response = sender.send_request(request).await?;
+
+        let status = response.status();
+
+        let body = response.into_body()
+            .collect().await?
+            .to_bytes();
+
+        Ok(Response {
+            status: status.as_u16(),
+            body: Some(serde_json::from_slice(&body)?)
+        })
+    }
+}
+
+#[cfg(all(unix, feature = "client-unix"))]
+#[async_trait::async_trait]
+impl<T: HttpClient> HttpClient for UnixHttpClient<T> {
+    async fn get(&self, url: impl AsRef<str> + Send) -> Result<Response, Box<dyn std::error::Error + Send + Sync>> {
+        let Some((socket, path)) = ServerAddress::split_unix_endpoint(url.as_ref()) else {
+            return self.0.get(url).await;
+        };
+
+        let request = hyper::Request::get(path)
+            .header("Host", "localhost")
+            .body(Default::default())?;
+
+        Self::request(socket, request).await
+    }
+
+    async fn post(&self, url: impl AsRef<str> + Send, body: Json) -> Result<Response, Box<dyn std::error::Error + Send + Sync>> {
+        let Some((socket, path)) = ServerAddress::split_unix_endpoint(url.as_ref()) else {
+            return self.0.post(url, body).await;
+        };
+
+        let request = hyper::Request::post(path)
+            .header("Host", "localhost")
+            .header("Content-Type", "application/json")
+            .body(serde_json::to_vec(&body)?.into())?;
+
+        Self::request(socket, request).await
+    }
+
+    async fn websocket(&self, url: impl AsRef<str> + Send) -> Result<BoxedWebSocket, Box<dyn std::error::Error + Send + Sync>> {
+        if ServerAddress::split_unix_endpoint(url.as_ref()).is_some() {
+            return Err("WebSockets are not supported for Unix domain sockets".into());
+        }
+
+        self.0.websocket(url).await
+    }
+}
+
+#[cfg(all(test, unix, feature = "client-unix", feature = "client-reqwest", feature = "server-axum-unix"))]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+    use std::time::Duration;
+
+    use crate::http::{HttpServer, AxumHttpServer};
+    use crate::crypto::prelude::*;
+    use crate::rest_api::prelude::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn unix() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-unix-socket-test-{}", std::process::id()));
+
+        std::fs::create_dir_all(&folder)?;
+
+        let socket = folder.join("server.sock");
+
+        let mut server = AxumHttpServer::default()
+            .with_unix_socket_mode(0o600);
+
+        server.get("/api/v1/servers", |client_address| async move {
+            assert!(client_address.ip().is_loopback());
+
+            ServersResponse::new([])
+        }).await;
+
+        server.post("/api/v1/servers", |_, request: ServersResponse| async move {
+            request
+        }).await;
+
+        let server = tokio::spawn({
+            let socket = socket.clone();
+
+            async move {
+                server.serve_unix(socket).await
+                    .map_err(|err| err.to_string())
+            }
+        });
+
+        let address = ServerAddress::new("unix", socket.to_string_lossy(), None, "");
+
+        let client = UnixHttpClient::new(ReqwestHttpClient::default());
+
+        let mut response = None;
+
+        for _ in 0..50 {
+            if let Ok(servers) = client.get_request::<ServersResponse>(address.endpoint("/api/v1/servers")).await {
+                response = Some(servers);
+
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        assert_eq!(response, Some(ServersResponse::new([])));
+
+        assert_eq!(std::fs::metadata(&socket)?.permissions().mode() & 0o777, 0o600);
+
+        // Temporary folder used to bind the socket is removed
+        assert_eq!(std::fs::read_dir(&folder)?.count(), 1);
+
+        let request = ServersResponse::new([
+            Server::new(SecretKey::random().public_key(), "example.org".parse()?)
+        ]);
+
+        let response = client.post_request::<ServersResponse, ServersResponse>(
+            address.endpoint("/api/v1/servers"),
+            request.clone()
+        ).await?;
+
+        assert_eq!(response, request);
+
+        server.abort();
+
+        std::fs::remove_dir_all(folder)?;
+
+        Ok(())
+    }
 }
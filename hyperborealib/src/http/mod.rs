@@ -1,11 +1,19 @@
 pub mod client;
 pub mod server;
+pub mod websocket;
 
 pub use client::HttpClient;
 pub use server::HttpServer;
+pub use websocket::{WebSocket, BoxedWebSocket};
 
 #[cfg(feature = "client-reqwest")]
 pub use client::ReqwestHttpClient;
 
+#[cfg(all(unix, feature = "client-unix"))]
+pub use client::UnixHttpClient;
+
 #[cfg(feature = "server-axum")]
 pub use server::AxumHttpServer;
+
+#[cfg(feature = "server-axum-tls")]
+pub use server::AxumTlsConfig;
@@ -3,6 +3,11 @@ use std::net::{
     ToSocketAddrs
 };
 
+use std::path::Path;
+
+#[cfg(feature = "server-axum-tls")]
+use std::path::PathBuf;
+
 #[cfg(feature = "server-axum")]
 use tokio::net::TcpListener;
 
@@ -14,6 +19,8 @@ use axum::{
 
 use crate::rest_api::AsJson;
 
+use super::websocket::BoxedWebSocket;
+
 #[async_trait::async_trait]
 pub trait HttpServer {
     /// Add GET request route
@@ -30,13 +37,173 @@ pub trait HttpServer {
         callback: impl FnOnce(SocketAddr, T) -> R + Clone + Send + Sync + 'static
     );
 
+    /// Add WebSocket route
+    /// 
+    /// Default implementation doesn't add the route
+    /// for the servers which don't support WebSockets.
+    async fn websocket<F: std::future::Future<Output = ()> + Send + 'static>(
+        &mut self,
+        path: impl AsRef<str> + Send,
+        callback: impl FnOnce(SocketAddr, BoxedWebSocket) -> F + Clone + Send + Sync + 'static
+    ) {
+        let _ = (path, callback);
+    }
+
     /// Run the server with specified GET and POST routes
     async fn serve(self, address: impl ToSocketAddrs + Send) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// Run the server with specified GET and POST routes
+    /// on the Unix domain socket.
+    /// 
+    /// Default implementation returns an error
+    /// for the servers which don't support this.
+    async fn serve_unix(self, path: impl AsRef<Path> + Send) -> Result<(), Box<dyn std::error::Error>>
+    where
+        Self: Sized + Send
+    {
+        let _ = path;
+
+        Err("Unix domain sockets are not supported by this HTTP server".into())
+    }
+}
+
+#[cfg(feature = "server-axum-tls")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Paths to the PEM encoded TLS certificate chain
+/// and private key used by the `AxumHttpServer`.
+pub struct AxumTlsConfig {
+    pub certificate: PathBuf,
+    pub private_key: PathBuf
+}
+
+#[cfg(feature = "server-axum")]
+#[derive(Clone)]
+/// Future resolved when the `AxumHttpServer` should be stopped.
+struct AxumShutdownSignal(futures::future::Shared<futures::future::BoxFuture<'static, ()>>);
+
+#[cfg(feature = "server-axum")]
+impl std::fmt::Debug for AxumShutdownSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AxumShutdownSignal").finish_non_exhaustive()
+    }
 }
 
 #[cfg(feature = "server-axum")]
 #[derive(Default, Debug, Clone)]
-pub struct AxumHttpServer(Option<axum::Router>);
+pub struct AxumHttpServer {
+    router: Option<axum::Router>,
+    shutdown: Option<AxumShutdownSignal>,
+
+    #[cfg(feature = "server-axum-tls")]
+    tls: Option<AxumTlsConfig>,
+
+    #[cfg(all(unix, feature = "server-axum-unix"))]
+    unix_socket_mode: Option<u32>
+}
+
+#[cfg(feature = "server-axum")]
+impl AxumHttpServer {
+    /// Stop the server gracefully when the given future resolves.
+    /// 
+    /// The server stops accepting new connections and waits
+    /// until the already accepted ones finish their requests.
+    /// Cloned servers share the same signal, so it can stop
+    /// both TCP and Unix domain socket servers.
+    /// 
+    /// ```rust
+    /// use hyperborealib::http::AxumHttpServer;
+    /// 
+    /// let server = AxumHttpServer::default()
+    ///     .with_shutdown_signal(std::future::pending());
+    /// ```
+    pub fn with_shutdown_signal(mut self, signal: impl std::future::Future<Output = ()> + Send + 'static) -> Self {
+        use futures::FutureExt;
+
+        self.shutdown = Some(AxumShutdownSignal(signal.boxed().shared()));
+
+        self
+    }
+
+    /// Get future resolved when the server should be stopped.
+    fn shutdown_signal(&self) -> impl std::future::Future<Output = ()> + Send + 'static {
+        let signal = self.shutdown.clone();
+
+        async move {
+            match signal {
+                Some(signal) => signal.0.await,
+                None => std::future::pending().await
+            }
+        }
+    }
+}
+
+#[cfg(all(unix, feature = "server-axum-unix"))]
+impl AxumHttpServer {
+    /// Set permissions of the Unix domain socket file
+    /// created by the `serve_unix` method.
+    /// 
+    /// Only processes which can write to the socket file
+    /// are able to connect to it, so this can be used
+    /// as a local access control. E.g. `0o660` allows
+    /// only the owner and the group to use the server.
+    /// 
+    /// Socket is created in a temporary folder accessible
+    /// only by the current user and moved to the requested
+    /// path after its permissions are changed, so nobody
+    /// else can connect to it before that.
+    /// 
+    /// ```rust
+    /// use hyperborealib::http::AxumHttpServer;
+    /// 
+    /// let server = AxumHttpServer::default()
+    ///     .with_unix_socket_mode(0o660);
+    /// ```
+    pub fn with_unix_socket_mode(mut self, mode: u32) -> Self {
+        self.unix_socket_mode = Some(mode);
+
+        self
+    }
+
+    #[inline]
+    pub fn unix_socket_mode(&self) -> Option<u32> {
+        self.unix_socket_mode
+    }
+}
+
+#[cfg(feature = "server-axum-tls")]
+impl AxumHttpServer {
+    /// Create HTTP server which will accept
+    /// only HTTPS connections.
+    /// 
+    /// - `certificate` must contain path to the PEM
+    ///   encoded certificate chain of the server.
+    /// 
+    /// - `private_key` must contain path to the PEM
+    ///   encoded private key of the certificate.
+    /// 
+    /// Files are read when the server is started.
+    /// 
+    /// ```rust
+    /// use hyperborealib::http::AxumHttpServer;
+    /// 
+    /// let server = AxumHttpServer::with_tls("cert.pem", "key.pem");
+    /// ```
+    pub fn with_tls(certificate: impl Into<PathBuf>, private_key: impl Into<PathBuf>) -> Self {
+        Self {
+            tls: Some(AxumTlsConfig {
+                certificate: certificate.into(),
+                private_key: private_key.into()
+            }),
+
+            ..Self::default()
+        }
+    }
+
+    #[inline]
+    pub fn tls(&self) -> Option<&AxumTlsConfig> {
+        self.tls.as_ref()
+    }
+}
 
 #[cfg(feature = "server-axum")]
 #[async_trait::async_trait]
@@ -46,9 +213,9 @@ impl HttpServer for AxumHttpServer {
         path: impl AsRef<str> + Send,
         callback: impl FnOnce(SocketAddr) -> F + Clone + Send + Sync + 'static
     ) {
-        let router = self.0.take().unwrap_or_default();
+        let router = self.router.take().unwrap_or_default();
 
-        self.0 = Some(router.route(path.as_ref(), axum::routing::get(move |ConnectInfo(client_address): ConnectInfo<SocketAddr>| async move {
+        self.router = Some(router.route(path.as_ref(), axum::routing::get(move |ConnectInfo(client_address): ConnectInfo<SocketAddr>| async move {
             let response = callback(client_address).await;
 
             match response.to_json() {
@@ -74,9 +241,9 @@ impl HttpServer for AxumHttpServer {
         path: impl AsRef<str> + Send,
         callback: impl FnOnce(SocketAddr, T) -> R + Clone + Send + Sync + 'static
     ) {
-        let router = self.0.take().unwrap_or_default();
+        let router = self.router.take().unwrap_or_default();
 
-        self.0 = Some(router.route(path.as_ref(), axum::routing::post(move |ConnectInfo(client_address): ConnectInfo<SocketAddr>, body: HttpBody| async move {
+        self.router = Some(router.route(path.as_ref(), axum::routing::post(move |ConnectInfo(client_address): ConnectInfo<SocketAddr>, body: HttpBody| async move {
             let json = match serde_json::from_slice::<serde_json::Value>(&body) {
                 Ok(json) => json,
                 Err(err) => {
@@ -117,8 +284,25 @@ impl HttpServer for AxumHttpServer {
         })));
     }
 
+    #[cfg(feature = "server-axum-ws")]
+    async fn websocket<F: std::future::Future<Output = ()> + Send + 'static>(
+        &mut self,
+        path: impl AsRef<str> + Send,
+        callback: impl FnOnce(SocketAddr, BoxedWebSocket) -> F + Clone + Send + Sync + 'static
+    ) {
+        use axum::extract::ws::WebSocketUpgrade;
+
+        use super::websocket::AxumWebSocket;
+
+        let router = self.router.take().unwrap_or_default();
+
+        self.router = Some(router.route(path.as_ref(), axum::routing::get(move |ConnectInfo(client_address): ConnectInfo<SocketAddr>, upgrade: WebSocketUpgrade| async move {
+            upgrade.on_upgrade(move |socket| callback(client_address, Box::new(AxumWebSocket(socket))))
+        })));
+    }
+
     async fn serve(mut self, address: impl ToSocketAddrs + Send) -> Result<(), Box<dyn std::error::Error>> {
-        let router = self.0.take()
+        let router = self.router.take()
             .unwrap_or_default()
             .into_make_service_with_connect_info::<SocketAddr>();
 
@@ -126,9 +310,263 @@ impl HttpServer for AxumHttpServer {
             return Err("Failed to resolve server address".into());
         };
 
+        #[cfg(feature = "server-axum-tls")]
+        if let Some(tls) = self.tls.take() {
+            let config = axum_server::tls_rustls::RustlsConfig::from_pem_file(
+                tls.certificate,
+                tls.private_key
+            ).await?;
+
+            let handle = axum_server::Handle::new();
+
+            tokio::spawn({
+                let handle = handle.clone();
+                let signal = self.shutdown_signal();
+
+                async move {
+                    signal.await;
+
+                    handle.graceful_shutdown(None);
+                }
+            });
+
+            axum_server::bind_rustls(address, config)
+                .handle(handle)
+                .serve(router)
+                .await?;
+
+            return Ok(());
+        }
+
         let listener = TcpListener::bind(address).await?;
 
-        axum::serve(listener, router).await?;
+        axum::serve(listener, router)
+            .with_graceful_shutdown(self.shutdown_signal())
+            .await?;
+
+        Ok(())
+    }
+
+    #[cfg(all(unix, feature = "server-axum-unix"))]
+    async fn serve_unix(mut self, path: impl AsRef<Path> + Send) -> Result<(), Box<dyn std::error::Error>> {
+        use std::os::unix::fs::FileTypeExt;
+
+        use hyper_util::rt::{TokioExecutor, TokioIo};
+        use hyper_util::service::TowerToHyperService;
+
+        let path = path.as_ref();
+
+        // Remove socket file left from the previous run
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if !metadata.file_type().is_socket() {
+                return Err(format!("File {path:?} already exists and is not a socket").into());
+            }
+
+            std::fs::remove_file(path)?;
+        }
+
+        let listener = match self.unix_socket_mode {
+            Some(mode) => bind_unix_socket(path, mode)?,
+            None => tokio::net::UnixListener::bind(path)?
+        };
+
+        // Unix sockets' clients have no IP address,
+        // so loopback is given to the routes callbacks
+        let router = self.router.take()
+            .unwrap_or_default()
+            .layer(axum::Extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 0)))));
+
+        let shutdown = self.shutdown_signal();
+
+        tokio::pin!(shutdown);
+
+        let mut connections = tokio::task::JoinSet::new();
+
+        loop {
+            let stream = tokio::select! {
+                stream = listener.accept() => stream?.0,
+
+                // Stop accepting new connections
+                _ = &mut shutdown => break
+            };
+
+            let service = TowerToHyperService::new(router.clone());
+            let shutdown = self.shutdown_signal();
+
+            connections.spawn(async move {
+                let builder = hyper_util::server::conn::auto::Builder::new(TokioExecutor::new());
+                let connection = builder.serve_connection(TokioIo::new(stream), service);
+
+                tokio::pin!(connection);
+
+                let result = tokio::select! {
+                    result = connection.as_mut() => result,
+
+                    // Finish current request and close the connection
+                    _ = shutdown => {
+                        connection.as_mut().graceful_shutdown();
+
+                        connection.await
+                    }
+                };
+
+                if let Err(_err) = result {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(err = ?_err, "Unix socket connection error");
+                }
+            });
+
+            // Forget finished connections
+            while connections.try_join_next().is_some() {}
+        }
+
+        while connections.join_next().await.is_some() {}
+
+        Ok(())
+    }
+}
+
+#[cfg(all(unix, feature = "server-axum-unix"))]
+/// Bind Unix domain socket with the given permissions.
+/// 
+/// Socket is created in a temporary folder accessible only
+/// by the current user and atomically moved to the given path
+/// when its permissions are set. Process umask is not changed
+/// since it's shared by all the threads.
+fn bind_unix_socket(path: &Path, mode: u32) -> std::io::Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new(".")
+    };
+
+    // Temporary folder must be on the same filesystem
+    // as the socket file, so it can be renamed
+    let temp_folder = parent.join(format!(".hyperborea-socket-{}", crate::crypto::utils::safe_random_u64()));
+
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&temp_folder)?;
+
+    let temp_path = temp_folder.join("socket");
+
+    let result = tokio::net::UnixListener::bind(&temp_path).and_then(|listener| {
+        std::fs::set_permissions(&temp_path, std::fs::Permissions::from_mode(mode))?;
+        std::fs::rename(&temp_path, path)?;
+
+        Ok(listener)
+    });
+
+    // Socket file is either moved or not needed anymore
+    let _ = std::fs::remove_file(&temp_path);
+    let _ = std::fs::remove_dir(&temp_folder);
+
+    result
+}
+
+#[cfg(all(test, feature = "server-axum-tls", feature = "client-reqwest"))]
+mod tests {
+    use std::time::Duration;
+
+    use crate::http::{HttpClient, ReqwestHttpClient};
+    use crate::rest_api::prelude::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn tls() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // Self-signed CA certificate
+        let mut ca_params = rcgen::CertificateParams::new(vec![]);
+
+        ca_params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+        ca_params.distinguished_name.push(rcgen::DnType::CommonName, "Hyperborea test CA");
+
+        let ca = rcgen::Certificate::from_params(ca_params)?;
+
+        // Server certificate signed by the CA
+        let certificate = rcgen::Certificate::from_params(
+            rcgen::CertificateParams::new(vec![String::from("localhost")])
+        )?;
+
+        let folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-axum-tls-test-{}", std::process::id()));
+
+        std::fs::create_dir_all(&folder)?;
+
+        std::fs::write(folder.join("cert.pem"), certificate.serialize_pem_with_signer(&ca)?)?;
+        std::fs::write(folder.join("key.pem"), certificate.serialize_private_key_pem())?;
+
+        let port = std::net::TcpListener::bind("127.0.0.1:0")?
+            .local_addr()?
+            .port();
+
+        let mut server = AxumHttpServer::with_tls(
+            folder.join("cert.pem"),
+            folder.join("key.pem")
+        );
+
+        server.get("/api/v1/servers", |_| async {
+            ServersResponse::new([])
+        }).await;
+
+        #[cfg(all(feature = "server-axum-ws", feature = "client-reqwest-ws"))]
+        server.websocket("/api/v1/echo", |_, mut socket| async move {
+            while let Some(Ok(message)) = socket.recv().await {
+                if socket.send(message).await.is_err() {
+                    break;
+                }
+            }
+        }).await;
+
+        let server = tokio::spawn(async move {
+            server.serve(("127.0.0.1", port)).await
+                .map_err(|err| err.to_string())
+        });
+
+        let url = format!("https://localhost:{port}/api/v1/servers");
+
+        let client = ReqwestHttpClient::with_ca_certificate(ca.serialize_pem()?)?;
+
+        let mut response = None;
+
+        for _ in 0..50 {
+            if let Ok(servers) = client.get_request::<ServersResponse>(&url).await {
+                response = Some(servers);
+
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        assert_eq!(response, Some(ServersResponse::new([])));
+
+        // WebSocket connections trust the same CA certificate
+        #[cfg(all(feature = "server-axum-ws", feature = "client-reqwest-ws"))]
+        {
+            let url = format!("https://localhost:{port}/api/v1/echo");
+
+            let mut socket = client.websocket(&url).await?;
+
+            socket.send(serde_json::json!({ "ping": 1 })).await?;
+
+            assert_eq!(socket.recv().await.transpose()?, Some(serde_json::json!({ "ping": 1 })));
+
+            socket.close().await?;
+
+            assert!(ReqwestHttpClient::default().websocket(&url).await.is_err());
+        }
+
+        // Self-signed certificate is not trusted by default
+        assert!(ReqwestHttpClient::default().get_request::<ServersResponse>(&url).await.is_err());
+
+        // Plain HTTP is not served
+        assert!(client.get_request::<ServersResponse>(format!("http://localhost:{port}/api/v1/servers")).await.is_err());
+
+        server.abort();
+
+        std::fs::remove_dir_all(folder)?;
 
         Ok(())
     }
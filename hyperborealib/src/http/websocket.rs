@@ -0,0 +1,114 @@
+use serde_json::Value as Json;
+
+#[async_trait::async_trait]
+/// WebSocket connection exchanging JSON messages.
+/// 
+/// This trait is implemented by the HTTP clients and servers
+/// wrappers to provide WebSocket routes independent from
+/// the underlying libraries.
+pub trait WebSocket: Send {
+    /// Send JSON message.
+    async fn send(&mut self, message: Json) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Receive next JSON message.
+    /// 
+    /// Return `None` if the connection is closed.
+    /// 
+    /// This method must be cancel safe, so it could
+    /// be raced with other futures.
+    async fn recv(&mut self) -> Option<Result<Json, Box<dyn std::error::Error + Send + Sync>>>;
+
+    /// Close the connection.
+    async fn close(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Type-erased WebSocket connection.
+pub type BoxedWebSocket = Box<dyn WebSocket>;
+
+#[cfg(feature = "server-axum-ws")]
+/// `WebSocket` implementation for the axum's WebSocket connections.
+pub struct AxumWebSocket(pub axum::extract::ws::WebSocket);
+
+#[cfg(feature = "server-axum-ws")]
+#[async_trait::async_trait]
+impl WebSocket for AxumWebSocket {
+    async fn send(&mut self, message: Json) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use axum::extract::ws::Message;
+
+        self.0.send(Message::Text(message.to_string())).await?;
+
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Option<Result<Json, Box<dyn std::error::Error + Send + Sync>>> {
+        use axum::extract::ws::Message;
+
+        loop {
+            let message = match self.0.recv().await? {
+                Ok(message) => message,
+                Err(err) => return Some(Err(err.into()))
+            };
+
+            match message {
+                Message::Text(text) => return Some(serde_json::from_str(&text).map_err(Into::into)),
+                Message::Binary(bytes) => return Some(serde_json::from_slice(&bytes).map_err(Into::into)),
+                Message::Close(_) => return None,
+
+                // Pings are answered by axum
+                Message::Ping(_) | Message::Pong(_) => continue
+            }
+        }
+    }
+
+    async fn close(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.0.send(axum::extract::ws::Message::Close(None)).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "client-reqwest-ws")]
+/// `WebSocket` implementation for the tungstenite's WebSocket connections.
+pub struct TungsteniteWebSocket(
+    pub tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>
+);
+
+#[cfg(feature = "client-reqwest-ws")]
+#[async_trait::async_trait]
+impl WebSocket for TungsteniteWebSocket {
+    async fn send(&mut self, message: Json) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use futures::SinkExt;
+        use tokio_tungstenite::tungstenite::Message;
+
+        self.0.send(Message::Text(message.to_string())).await?;
+
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Option<Result<Json, Box<dyn std::error::Error + Send + Sync>>> {
+        use futures::StreamExt;
+        use tokio_tungstenite::tungstenite::Message;
+
+        loop {
+            let message = match self.0.next().await? {
+                Ok(message) => message,
+                Err(err) => return Some(Err(err.into()))
+            };
+
+            match message {
+                Message::Text(text) => return Some(serde_json::from_str(&text).map_err(Into::into)),
+                Message::Binary(bytes) => return Some(serde_json::from_slice(&bytes).map_err(Into::into)),
+                Message::Close(_) => return None,
+
+                // Pings are answered by tungstenite
+                Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => continue
+            }
+        }
+    }
+
+    async fn close(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.0.close(None).await?;
+
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ pub mod http;
 pub mod drivers;
 pub mod rest_api;
 
-pub const STANDARD_VERSION: u64 = 1;
+pub const STANDARD_VERSION: u64 = 2;
 pub const LIBRARY_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub mod exports {
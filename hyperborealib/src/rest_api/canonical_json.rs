@@ -0,0 +1,113 @@
+use serde_json::Value as Json;
+
+/// Serialize JSON value to its canonical string representation.
+/// 
+/// Canonical representation doesn't contain any whitespaces,
+/// and objects' keys are sorted in lexicographical order
+/// of their UTF-8 bytes. Strings and numbers are serialized
+/// the same way as by `serde_json`.
+/// 
+/// This function is used to calculate request body digests
+/// which are independent from the fields order.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use serde_json::json;
+/// 
+/// use hyperborealib::rest_api::canonical_json::to_canonical_json;
+/// 
+/// let value = json!({
+///     "b": [1, 2, 3],
+///     "a": {
+///         "d": "Hello, World!",
+///         "c": null
+///     }
+/// });
+/// 
+/// assert_eq!(
+///     to_canonical_json(&value),
+///     r#"{"a":{"c":null,"d":"Hello, World!"},"b":[1,2,3]}"#
+/// );
+/// ```
+pub fn to_canonical_json(value: &Json) -> String {
+    let mut output = String::new();
+
+    write_value(&mut output, value);
+
+    output
+}
+
+fn write_value(output: &mut String, value: &Json) {
+    match value {
+        Json::Array(values) => {
+            output.push('[');
+
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+
+                write_value(output, value);
+            }
+
+            output.push(']');
+        }
+
+        Json::Object(values) => {
+            let mut values = values.iter().collect::<Vec<_>>();
+
+            values.sort_by(|a, b| a.0.cmp(b.0));
+
+            output.push('{');
+
+            for (i, (key, value)) in values.into_iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+
+                output.push_str(&Json::String(key.clone()).to_string());
+                output.push(':');
+
+                write_value(output, value);
+            }
+
+            output.push('}');
+        }
+
+        // Null, booleans, numbers and strings
+        value => output.push_str(&value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn canonical() {
+        let a = json!({
+            "standard": 2,
+            "proof": {
+                "seed": 1,
+                "sign": "c2lnbg=="
+            },
+            "request": ["Hello", "\"World\"", 1.5, true, null]
+        });
+
+        let b = serde_json::from_str::<Json>(r#"{
+            "request": ["Hello", "\"World\"", 1.5, true, null],
+            "proof": { "sign": "c2lnbg==", "seed": 1 },
+            "standard": 2
+        }"#).unwrap();
+
+        assert_eq!(to_canonical_json(&a), to_canonical_json(&b));
+
+        assert_eq!(
+            to_canonical_json(&a),
+            r#"{"proof":{"seed":1,"sign":"c2lnbg=="},"request":["Hello","\"World\"",1.5,true,null],"standard":2}"#
+        );
+    }
+}
@@ -1,5 +1,8 @@
-use std::sync::Arc;
-use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use futures::StreamExt;
 
 use crate::crypto::asymmetric::PublicKey;
 use crate::http::client::HttpClient;
@@ -11,16 +14,58 @@ use crate::rest_api::prelude::{
     Server as ServerApiRecord
 };
 
-use super::Error;
+use super::{Error, LookupCache};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Client-side lookup parameters.
+/// 
+/// Used by the `ConnectedClient::lookup` method.
+pub struct LookupParams {
+    /// Maximal number of simultaneously requested servers.
+    pub concurrency: usize,
+
+    /// Maximal number of hint servers in a chain
+    /// followed after the connected server.
+    pub max_hops: u64,
+
+    /// Maximal number of hint servers
+    /// requested on a single hop.
+    /// 
+    /// Extra hints are ignored.
+    pub max_width: usize,
+
+    /// Maximal time spent on a single lookup,
+    /// including the time of pending requests.
+    pub timeout: Duration
+}
+
+impl Default for LookupParams {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            max_hops: 4,
+            max_width: 16,
+            timeout: Duration::from_secs(30)
+        }
+    }
+}
+
+/// Default maximal number of messages sent
+/// in a single `POST /api/v1/send_batch` request.
+/// 
+/// Equals to the default limit of the servers.
+pub const DEFAULT_MAX_BATCH_SIZE: u64 = 128;
 
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone)]
 /// Client HTTP middleware
 /// 
 /// This struct is used to perform HTTP REST API requests
 /// to the servers from the name of inner client driver.
 pub struct Client<T> {
     http_client: Arc<T>,
-    driver: Arc<ClientDriver>
+    driver: Arc<ClientDriver>,
+    lookup_params: LookupParams,
+    lookup_cache: Option<Arc<LookupCache>>
 }
 
 impl<T: HttpClient + Send + Sync> Client<T> {
@@ -35,10 +80,37 @@ impl<T: HttpClient + Send + Sync> Client<T> {
 
         Self {
             http_client: Arc::new(http_client),
-            driver: Arc::new(client_driver)
+            driver: Arc::new(client_driver),
+            lookup_params: LookupParams::default(),
+            lookup_cache: None
         }
     }
 
+    #[inline]
+    /// Change client-side lookup parameters
+    /// of the connected clients.
+    pub fn with_lookup_params(mut self, params: LookupParams) -> Self {
+        self.lookup_params = params;
+
+        self
+    }
+
+    #[inline]
+    /// Cache lookup results of the connected clients.
+    /// 
+    /// The cache is shared by all the clients
+    /// connected using this middleware.
+    pub fn with_lookup_cache(mut self, cache: LookupCache) -> Self {
+        self.lookup_cache = Some(Arc::new(cache));
+
+        self
+    }
+
+    #[inline]
+    pub fn lookup_cache(&self) -> Option<&LookupCache> {
+        self.lookup_cache.as_deref()
+    }
+
     #[inline]
     pub fn http_client(&self) -> Arc<T> {
         self.http_client.clone()
@@ -68,13 +140,16 @@ impl<T: HttpClient + Send + Sync> Client<T> {
     /// 
     /// - `server_address` must contain address of the server
     ///   from which we want to request the info.
-    pub async fn get_info(&self, server_address: impl std::fmt::Display) -> Result<InfoResponse, Error> {
+    /// 
+    /// Returned response can contain server's statistics
+    /// in the `stats` field if the server supports them.
+    pub async fn get_info(&self, server_address: &ServerAddress) -> Result<InfoResponse, Error> {
         #[cfg(feature = "tracing")]
         tracing::debug!("Sending GET /api/v1/info request");
 
         // Send get info request
         let response = self.http_client.get_request::<InfoResponse>(
-            format!("http://{server_address}/api/v1/info")
+            server_address.endpoint("/api/v1/info")
         ).await?;
 
         // Validate response
@@ -94,13 +169,13 @@ impl<T: HttpClient + Send + Sync> Client<T> {
     /// 
     /// - `server_address` must contain address of the server
     ///   from which we want to request the clients list.
-    pub async fn get_clients(&self, server_address: impl std::fmt::Display) -> Result<Vec<ClientApiRecord>, Error> {
+    pub async fn get_clients(&self, server_address: &ServerAddress) -> Result<Vec<ClientApiRecord>, Error> {
         #[cfg(feature = "tracing")]
         tracing::debug!("Sending GET /api/v1/clients request");
 
         // Send get clients request
         let response = self.http_client.get_request::<ClientsResponse>(
-            format!("http://{server_address}/api/v1/clients")
+            server_address.endpoint("/api/v1/clients")
         ).await?;
 
         Ok(response.clients)
@@ -115,18 +190,64 @@ impl<T: HttpClient + Send + Sync> Client<T> {
     /// 
     /// - `server_address` must contain address of the server
     ///   from which we want to request the servers list.
-    pub async fn get_servers(&self, server_address: impl std::fmt::Display) -> Result<Vec<ServerApiRecord>, Error> {
+    pub async fn get_servers(&self, server_address: &ServerAddress) -> Result<Vec<ServerApiRecord>, Error> {
         #[cfg(feature = "tracing")]
         tracing::debug!("Sending GET /api/v1/servers request");
 
         // Send get servers request
         let response = self.http_client.get_request::<ServersResponse>(
-            format!("http://{server_address}/api/v1/servers")
+            server_address.endpoint("/api/v1/servers")
         ).await?;
 
         Ok(response.servers)
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(ret, skip_all, fields(
+        server_address
+    )))]
+    /// Announce given server about some another server
+    /// or a (client, server) pair.
+    /// 
+    /// This method will perform `POST /api/v1/announce` request.
+    /// 
+    /// - `server_address` must contain address of the server
+    ///   which we want to announce.
+    /// 
+    /// - `announcement` must contain information about the
+    ///   announced server or client. Announced client's
+    ///   connection certificate must be issued for
+    ///   the announced server.
+    pub async fn announce(&self, server_address: &ServerAddress, announcement: AnnounceRequestBody) -> Result<(), Error> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Sending POST /api/v1/announce request");
+
+        // Prepare announce request
+        let request = AnnounceRequest(Request::new(self.driver.secret_key(), announcement));
+
+        let proof_seed = request.0.proof_seed;
+
+        // Send request
+        let response = self.http_client.post_request::<AnnounceRequest, AnnounceResponse>(
+            server_address.endpoint("/api/v1/announce"),
+            request
+        ).await?;
+
+        // Validate response
+        if !response.validate(proof_seed)? {
+            return Err(Error::InvalidProofSeedSignature);
+        }
+
+        // Check response status
+        if let Response::Error { status, reason, .. } = response.0 {
+            return Err(Error::RequestFailed {
+                status,
+                reason
+            });
+        }
+
+        Ok(())
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(
         server_address
     )))]
@@ -137,8 +258,8 @@ impl<T: HttpClient + Send + Sync> Client<T> {
     /// 
     /// - `server_address` must contain address of the server
     ///   to which we want to connect.
-    pub async fn connect(&self, server_address: impl std::fmt::Display + Clone) -> Result<ConnectedClient<T>, Error> {
-        let server_info = self.get_info(server_address.clone()).await?;
+    pub async fn connect(&self, server_address: &ServerAddress) -> Result<ConnectedClient<T>, Error> {
+        let server_info = self.get_info(server_address).await?;
 
         self.connect_to(server_address, server_info.public_key).await
     }
@@ -154,7 +275,7 @@ impl<T: HttpClient + Send + Sync> Client<T> {
     /// In this method we expect that the given server has
     /// given public key. We need it to create connection
     /// certificate.
-    pub async fn connect_to(&self, server_address: impl std::fmt::Display, server_public: PublicKey) -> Result<ConnectedClient<T>, Error> {
+    pub async fn connect_to(&self, server_address: &ServerAddress, server_public: PublicKey) -> Result<ConnectedClient<T>, Error> {
         #[cfg(feature = "tracing")]
         tracing::debug!("Sending POST /api/v1/connect request");
 
@@ -170,7 +291,7 @@ impl<T: HttpClient + Send + Sync> Client<T> {
 
         // Send request
         let response = self.http_client.post_request::<ConnectRequest, ConnectResponse>(
-            format!("http://{server_address}/api/v1/connect"),
+            server_address.endpoint("/api/v1/connect"),
             request
         ).await?;
 
@@ -187,9 +308,13 @@ impl<T: HttpClient + Send + Sync> Client<T> {
                     driver: self.driver.clone(),
                     connected_server: ServerApiRecord {
                         public_key: server_public,
-                        address: server_address.to_string()
+                        address: server_address.clone()
                     },
-                    connection_certificate: certificate
+                    connection_certificate: certificate,
+                    lookup_params: self.lookup_params,
+                    lookup_cache: self.lookup_cache.clone(),
+                    max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+                    announced_to: Arc::new(Mutex::new(HashSet::new()))
                 };
 
                 Ok(client)
@@ -205,7 +330,7 @@ impl<T: HttpClient + Send + Sync> Client<T> {
     }
 }
 
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone)]
 /// Connected client HTTP middleware
 /// 
 /// This struct is used to perform HTTP REST API requests
@@ -218,7 +343,14 @@ pub struct ConnectedClient<T> {
     http_client: Arc<T>,
     driver: Arc<ClientDriver>,
     connected_server: ServerApiRecord,
-    connection_certificate: ConnectionCertificate
+    connection_certificate: ConnectionCertificate,
+    lookup_params: LookupParams,
+    lookup_cache: Option<Arc<LookupCache>>,
+    max_batch_size: u64,
+
+    /// Public keys of the servers which accepted
+    /// announcements of this connection.
+    announced_to: Arc<Mutex<HashSet<PublicKey>>>
 }
 
 impl<T: HttpClient> ConnectedClient<T> {
@@ -252,6 +384,50 @@ impl<T: HttpClient> ConnectedClient<T> {
         &self.connection_certificate
     }
 
+    #[inline]
+    pub fn lookup_params(&self) -> &LookupParams {
+        &self.lookup_params
+    }
+
+    #[inline]
+    /// Change client-side lookup parameters.
+    pub fn with_lookup_params(mut self, params: LookupParams) -> Self {
+        self.lookup_params = params;
+
+        self
+    }
+
+    #[inline]
+    pub fn lookup_cache(&self) -> Option<&LookupCache> {
+        self.lookup_cache.as_deref()
+    }
+
+    #[inline]
+    /// Cache lookup results of this client.
+    pub fn with_lookup_cache(mut self, cache: LookupCache) -> Self {
+        self.lookup_cache = Some(Arc::new(cache));
+
+        self
+    }
+
+    #[inline]
+    pub fn max_batch_size(&self) -> u64 {
+        self.max_batch_size
+    }
+
+    #[inline]
+    /// Change maximal number of messages sent in a single
+    /// `POST /api/v1/send_batch` request.
+    /// 
+    /// It must not exceed the limit of the receivers'
+    /// servers, otherwise their requests are rejected.
+    /// Default value is `DEFAULT_MAX_BATCH_SIZE`.
+    pub fn with_max_batch_size(mut self, max_batch_size: u64) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+
+        self
+    }
+
     /// Construct new `Client` struct from the protocol's paper.
     /// 
     /// Service function used by other methods in this struct.
@@ -272,7 +448,7 @@ impl<T: HttpClient> ConnectedClient<T> {
     /// 
     /// - `server` should contain address of the server
     ///   you want to announce about the current client.
-    pub async fn announce(&self, server: impl std::fmt::Display) -> Result<(), Error> {
+    pub async fn announce(&self, server: &ServerAddress) -> Result<(), Error> {
         #[cfg(feature = "tracing")]
         tracing::debug!("Sending POST /api/v1/announce request");
 
@@ -287,7 +463,7 @@ impl<T: HttpClient> ConnectedClient<T> {
 
         // Send request
         let response = self.http_client.post_request::<AnnounceRequest, AnnounceResponse>(
-            format!("http://{server}/api/v1/announce"),
+            server.endpoint("/api/v1/announce"),
             request
         ).await?;
 
@@ -297,14 +473,87 @@ impl<T: HttpClient> ConnectedClient<T> {
         }
 
         // Check response status
-        if let Response::Error { status, reason, .. } = response.0 {
-            return Err(Error::RequestFailed {
-                status,
-                reason
-            });
+        match response.0 {
+            Response::Success { public_key, .. } => {
+                // Remember the server to propagate disconnection to it
+                self.announced_to.lock()
+                    .expect("Failed to lock announced servers")
+                    .insert(public_key);
+
+                Ok(())
+            }
+
+            Response::Error { status, reason, .. } => {
+                Err(Error::RequestFailed {
+                    status,
+                    reason
+                })
+            }
         }
+    }
 
-        Ok(())
+    #[cfg_attr(feature = "tracing", tracing::instrument(ret, skip_all, fields(
+        return_inbox
+    )))]
+    /// Disconnect from the connected server.
+    /// 
+    /// This method will perform `POST /api/v1/disconnect` request.
+    /// 
+    /// - `return_inbox` specifies whether messages pending
+    ///   in the client's inbox should be returned or dropped.
+    /// 
+    /// The server removes the client from its routing table
+    /// and propagates disconnection to the servers which
+    /// accepted the client's announcements made by the
+    /// `announce` method. Use `connect` method to connect again.
+    /// 
+    /// This method will return list of messages removed
+    /// from the client's inbox, or an empty list if they
+    /// were dropped.
+    pub async fn disconnect(self, return_inbox: bool) -> Result<Vec<MessageInfo>, Error> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Sending POST /api/v1/disconnect request");
+
+        // Prepare disconnect request
+        let announced_to = self.announced_to.lock()
+            .expect("Failed to lock announced servers")
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let request = DisconnectRequest::new(
+            self.driver.secret_key(),
+            self.connection_certificate.clone(),
+            return_inbox,
+            announced_to
+        );
+
+        let proof_seed = request.0.proof_seed;
+
+        // Send request
+        let response = self.http_client.post_request::<DisconnectRequest, DisconnectResponse>(
+            self.connected_server.address.endpoint("/api/v1/disconnect"),
+            request
+        ).await?;
+
+        // Validate response
+        if !response.validate(proof_seed)? {
+            return Err(Error::InvalidProofSeedSignature);
+        }
+
+        // Check response status
+        match response.0 {
+            Response::Success { response, .. } => {
+                Ok(response.messages)
+            }
+
+            Response::Error { status, reason, .. } => {
+                Err(Error::RequestFailed {
+                    status,
+                    reason
+                })
+            }
+        }
     }
 
     #[cfg_attr(feature = "tracing", tracing::instrument(ret, skip_all, fields(
@@ -328,69 +577,252 @@ impl<T: HttpClient> ConnectedClient<T> {
     /// if you don't trust this server.
     /// 
     /// This method will keep requesting servers until no more
-    /// hints returned or needed client is found.
+    /// hints returned or needed client is found, within the
+    /// limits of the lookup params (see `LookupParams`).
+    /// Hinted servers are requested concurrently, unavailable
+    /// servers are skipped, and clients with certificates
+    /// not issued for the returned servers are ignored.
+    /// 
+    /// If the lookup cache is set (see `LookupCache`), then
+    /// cached results are returned without network requests.
     pub async fn lookup(&self, client_public: PublicKey, client_type: Option<ClientType>) -> Result<Option<(ClientApiRecord, ServerApiRecord, bool)>, Error> {
+        if let Some(record) = self.cached_lookup(&client_public, client_type) {
+            return Ok(Some(record));
+        }
+
+        let record = self.lookup_network(client_public.clone(), client_type).await?;
+
+        self.cache_lookup(client_public, client_type, &record);
+
+        Ok(record)
+    }
+
+    /// Check that the found client has the requested public key
+    /// and is connected to the server it was found on.
+    fn verify_lookup(client_public: &PublicKey, client: &ClientApiRecord, server: &ServerApiRecord) -> bool {
+        &client.public_key == client_public &&
+            client.certificate.validate(&client.public_key, &server.public_key).unwrap_or(false)
+    }
+
+    /// Get cached lookup result if the lookup cache is set.
+    fn cached_lookup(&self, client_public: &PublicKey, client_type: Option<ClientType>) -> Option<(ClientApiRecord, ServerApiRecord, bool)> {
+        self.lookup_cache.as_ref()?.get(client_public, client_type)
+    }
+
+    /// Cache found client if the lookup cache is set.
+    fn cache_lookup(&self, client_public: PublicKey, client_type: Option<ClientType>, record: &Option<(ClientApiRecord, ServerApiRecord, bool)>) {
+        if let (Some(cache), Some(record)) = (&self.lookup_cache, record) {
+            cache.insert(client_public, client_type, record.clone());
+        }
+    }
+
+    /// Remove cached records of the receiver which
+    /// is not connected to the given server anymore.
+    fn invalidate_lookup(&self, client_public: &PublicKey, server_address: &ServerAddress) {
+        if let Some(cache) = &self.lookup_cache {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(%server_address, "Invalidating cached lookup result");
+
+            cache.invalidate(client_public, server_address);
+        }
+    }
+
+    /// Lookup given client over the network.
+    async fn lookup_network(&self, client_public: PublicKey, client_type: Option<ClientType>) -> Result<Option<(ClientApiRecord, ServerApiRecord, bool)>, Error> {
+        let deadline = tokio::time::Instant::now() + self.lookup_params.timeout;
+
         // Prepare lookup request
-        let request = LookupRequest::new(self.driver.secret_key(), client_public, client_type);
+        let request = LookupRequest::new(self.driver.secret_key(), client_public.clone(), client_type);
 
         let proof_seed = request.0.proof_seed;
 
-        // Queue of search hints
-        let mut queue = VecDeque::from([
-            self.connected_server.address.clone()
+        // Store used servers to prevent infinite lookup loops
+        let mut used_servers = HashSet::from([
+            self.connected_server.public_key.clone()
         ]);
 
-        // Store used servers to prevent infinite lookup loops
-        let mut used_servers = HashSet::new();
+        let mut layer = vec![self.connected_server.clone()];
 
-        while let Some(server_address) = queue.pop_front() {
-            // Skip server if it was already used
-            if used_servers.contains(&server_address) {
-                continue;
+        for _ in 0..=self.lookup_params.max_hops {
+            if layer.is_empty() {
+                break;
             }
 
-            #[cfg(feature = "tracing")]
-            tracing::debug!(server_address, "Sending POST /api/v1/lookup request");
-
-            // Send lookup request
-            let response = self.http_client.post_request::<LookupRequest, LookupResponse>(
-                format!("http://{server_address}/api/v1/lookup"),
-                request.clone()
-            ).await?;
-
-            // Validate response
-            if !response.validate(proof_seed)? {
-                // Skip execution and go to the next server
-                continue;
-            }
+            let mut responses = futures::stream::iter(layer)
+                .map(|server| {
+                    let request = request.clone();
+
+                    async move {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(server_address = %server.address, "Sending POST /api/v1/lookup request");
+
+                        let response = self.http_client.post_request::<LookupRequest, LookupResponse>(
+                            server.address.endpoint("/api/v1/lookup"),
+                            request
+                        ).await;
+
+                        (server, response)
+                    }
+                })
+                .buffer_unordered(self.lookup_params.concurrency.max(1));
+
+            let mut next_layer = Vec::new();
+
+            loop {
+                let (server, response) = match tokio::time::timeout_at(deadline, responses.next()).await {
+                    Ok(Some(response)) => response,
+                    Ok(None) => break,
+
+                    Err(_) => return Err(Error::LookupTimeout)
+                };
+
+                // Skip unavailable servers
+                let response = match response {
+                    Ok(response) => response,
+
+                    Err(_err) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(server_address = %server.address, err = _err.to_string(), "Failed to request server");
 
-            // Process successful response
-            if let Response::Success { public_key, response, .. } = response.0 {
-                match response {
-                    LookupResponseBody::Local { client, available } => {
-                        let server = ServerApiRecord::new(public_key, &server_address);
+                        continue;
+                    }
+                };
+
+                // Skip responses with invalid signatures
+                if !response.validate(proof_seed).unwrap_or(false) {
+                    continue;
+                }
 
-                        return Ok(Some((client, server, available)));
+                let Response::Success { public_key, response, .. } = response.0 else {
+                    continue;
+                };
+
+                let (client, server, available) = match response {
+                    // Server must sign the response with its own key
+                    LookupResponseBody::Local { client, available } if public_key == server.public_key => {
+                        (client, server, available)
                     }
 
                     LookupResponseBody::Remote { client, server, available } => {
-                        return Ok(Some((client, server, available)));
+                        (client, server, available)
                     }
 
-                    LookupResponseBody::Hint { mut servers } => {
-                        for server in servers.drain(..) {
-                            queue.push_back(server.address);
+                    LookupResponseBody::Hint { servers } => {
+                        // Skip already requested servers and keep the layer bounded
+                        for server in servers {
+                            if next_layer.len() >= self.lookup_params.max_width {
+                                break;
+                            }
+
+                            if used_servers.insert(server.public_key.clone()) {
+                                next_layer.push(server);
+                            }
                         }
+
+                        continue;
                     }
+
+                    _ => continue
+                };
+
+                // Verify that the client is connected to the returned server
+                if Self::verify_lookup(&client_public, &client, &server) {
+                    return Ok(Some((client, server, available)));
                 }
             }
 
-            used_servers.insert(server_address);
+            layer = next_layer;
         }
 
         Ok(None)
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(ret, skip_all, fields(
+        client_public = client_public.to_base64(),
+        client_type = ?client_type
+    )))]
+    /// Lookup given client using the connected server.
+    /// 
+    /// This method will perform recursive `POST /api/v1/lookup`
+    /// request, so the connected server will request hinted
+    /// servers itself instead of this client.
+    /// 
+    /// - `client_public` must be a public key of the client
+    ///   you need to find.
+    /// 
+    /// - `client_type` is an optional filter of the type
+    ///   of the client you need to find.
+    /// 
+    /// This method will return the same values as `lookup`
+    /// and use the same lookup cache. If the server doesn't
+    /// support recursive lookups, then this method will
+    /// fall back to the `lookup` method.
+    pub async fn lookup_recursive(&self, client_public: PublicKey, client_type: Option<ClientType>) -> Result<Option<(ClientApiRecord, ServerApiRecord, bool)>, Error> {
+        if let Some(record) = self.cached_lookup(&client_public, client_type) {
+            return Ok(Some(record));
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Sending recursive POST /api/v1/lookup request");
+
+        // Prepare lookup request
+        let request = LookupRequest(Request::new(
+            self.driver.secret_key(),
+            LookupRequestBody::new(client_public.clone(), client_type).with_recursion()
+        ));
+
+        let proof_seed = request.0.proof_seed;
+
+        // Send request
+        let response = self.http_client.post_request::<LookupRequest, LookupResponse>(
+            self.connected_server.address.endpoint("/api/v1/lookup"),
+            request
+        ).await?;
+
+        // Validate response
+        if !response.validate(proof_seed)? {
+            return Err(Error::InvalidProofSeedSignature);
+        }
+
+        // Check response status
+        let record = match response.0 {
+            Response::Success { response, .. } => match response {
+                LookupResponseBody::Local { client, available } => {
+                    Some((client, self.connected_server.clone(), available))
+                }
+
+                LookupResponseBody::Remote { client, server, available } => {
+                    Some((client, server, available))
+                }
+
+                // Server has ignored the recursion
+                LookupResponseBody::Hint { .. } => {
+                    self.lookup_network(client_public.clone(), client_type).await?
+                }
+            }
+
+            Response::Error { status: ResponseStatus::ClientNotFound, .. } => None,
+
+            Response::Error { status, reason, .. } => {
+                return Err(Error::RequestFailed {
+                    status,
+                    reason
+                });
+            }
+        };
+
+        // Verify that the client is connected to the returned server
+        if let Some((client, server, _)) = &record {
+            if !Self::verify_lookup(&client_public, client, server) {
+                return Err(Error::Other("Server returned a client with invalid connection certificate".into()));
+            }
+        }
+
+        self.cache_lookup(client_public, client_type, &record);
+
+        Ok(record)
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(ret, skip_all, fields(
         receiver_server,
         receiver_public = receiver_public.to_base64(),
@@ -412,7 +844,7 @@ impl<T: HttpClient> ConnectedClient<T> {
     ///   parts (modules).
     /// 
     /// - `message` should contain the message you want to send.
-    pub async fn send(&self, receiver_server: impl std::fmt::Display, receiver_public: PublicKey, channel: impl ToString, message: Message) -> Result<(), Error> {
+    pub async fn send(&self, receiver_server: &ServerAddress, receiver_public: PublicKey, channel: impl ToString, message: Message) -> Result<(), Error> {
         #[cfg(feature = "tracing")]
         tracing::debug!("Sending POST /api/v1/send request");
 
@@ -428,7 +860,7 @@ impl<T: HttpClient> ConnectedClient<T> {
         let request = SendRequest::new(
             self.driver.secret_key(),
             sender,
-            receiver_public,
+            receiver_public.clone(),
             channel,
             message
         );
@@ -437,7 +869,7 @@ impl<T: HttpClient> ConnectedClient<T> {
 
         // Send request
         let response = self.http_client.post_request::<SendRequest, SendResponse>(
-            format!("http://{receiver_server}/api/v1/send"),
+            receiver_server.endpoint("/api/v1/send"),
             request
         ).await?;
 
@@ -448,6 +880,11 @@ impl<T: HttpClient> ConnectedClient<T> {
 
         // Check response status
         if let Response::Error { status, reason, .. } = response.0 {
+            // Receiver has moved to another server
+            if status == ResponseStatus::ClientNotConnected {
+                self.invalidate_lookup(&receiver_public, receiver_server);
+            }
+
             return Err(Error::RequestFailed {
                 status,
                 reason
@@ -457,6 +894,139 @@ impl<T: HttpClient> ConnectedClient<T> {
         Ok(())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    /// Send many messages to remote clients.
+    /// 
+    /// This method will group the messages by the receivers' servers
+    /// and perform `POST /api/v1/send_batch` requests with at most
+    /// `max_batch_size` messages each. Requests are sent concurrently.
+    /// 
+    /// - `messages` must contain addresses of the servers to which
+    ///   the receivers are connected and the messages to send.
+    /// 
+    /// This method will return results of the messages
+    /// in the same order as they were given.
+    pub async fn send_batch(&self, messages: impl IntoIterator<Item = (ServerAddress, SendBatchMessage)>) -> Vec<Result<(), Error>> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Sending POST /api/v1/send_batch requests");
+
+        // Group messages by the receivers' servers
+        let mut batches = HashMap::<ServerAddress, (Vec<usize>, Vec<SendBatchMessage>)>::new();
+        let mut receivers = Vec::new();
+
+        for (receiver_server, message) in messages {
+            receivers.push((message.receiver_public.clone(), receiver_server.clone()));
+
+            let (indexes, messages) = batches.entry(receiver_server).or_default();
+
+            indexes.push(receivers.len() - 1);
+            messages.push(message);
+        }
+
+        let client = ClientApiRecord::new(
+            self.driver.secret_key().public_key(),
+            self.connection_certificate.clone(),
+            ClientInfo::thin()
+        );
+
+        let sender = Sender::new(client, self.connected_server.clone());
+
+        // Split large groups to not exceed servers' limits
+        let batch_size = self.max_batch_size as usize;
+
+        let requests = batches.into_iter().flat_map(|(receiver_server, (indexes, messages))| {
+            let batches = indexes.chunks(batch_size)
+                .zip(messages.chunks(batch_size))
+                .map(|(indexes, messages)| (receiver_server.clone(), indexes.to_vec(), messages.to_vec()))
+                .collect::<Vec<_>>();
+
+            batches
+        }).map(|(receiver_server, indexes, messages)| {
+            let request = SendBatchRequest::new(
+                self.driver.secret_key(),
+                sender.clone(),
+                messages
+            );
+
+            async move {
+                (indexes, self.send_batch_request(&receiver_server, request).await)
+            }
+        });
+
+        let mut results = (0..receivers.len()).map(|_| None).collect::<Vec<_>>();
+
+        for (indexes, response) in futures::future::join_all(requests).await {
+            match response {
+                Ok(batch_results) => {
+                    for (index, result) in indexes.into_iter().zip(batch_results) {
+                        // Receiver has moved to another server
+                        if result.status == ResponseStatus::ClientNotConnected {
+                            let (receiver_public, receiver_server) = &receivers[index];
+
+                            self.invalidate_lookup(receiver_public, receiver_server);
+                        }
+
+                        results[index] = Some(match result.status {
+                            ResponseStatus::Success => Ok(()),
+
+                            status => Err(Error::RequestFailed {
+                                status,
+                                reason: result.reason.unwrap_or_default()
+                            })
+                        });
+                    }
+                }
+
+                // Whole batch failed, so every message gets the same error
+                Err(err) => {
+                    let reason = err.to_string();
+
+                    for index in indexes {
+                        results[index] = Some(Err(match &err {
+                            Error::RequestFailed { status, reason } => Error::RequestFailed {
+                                status: *status,
+                                reason: reason.clone()
+                            },
+
+                            _ => Error::Other(reason.clone().into())
+                        }));
+                    }
+                }
+            }
+        }
+
+        results.into_iter()
+            .map(|result| result.unwrap_or_else(|| Err(Error::Other("Server returned no result for the message".into()))))
+            .collect()
+    }
+
+    async fn send_batch_request(&self, receiver_server: &ServerAddress, request: SendBatchRequest) -> Result<Vec<SendBatchResult>, Error> {
+        let proof_seed = request.0.proof_seed;
+
+        // Send request
+        let response = self.http_client.post_request::<SendBatchRequest, SendBatchResponse>(
+            receiver_server.endpoint("/api/v1/send_batch"),
+            request
+        ).await?;
+
+        // Validate response
+        if !response.validate(proof_seed)? {
+            return Err(Error::InvalidProofSeedSignature);
+        }
+
+        // Check response status
+        match response.0 {
+            Response::Success { response, .. } => Ok(response.results),
+
+            Response::Error { status, reason, .. } => {
+                Err(Error::RequestFailed {
+                    status,
+                    reason
+                })
+            }
+        }
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(ret, skip_all, fields(
         channel = channel.to_string(),
         limit
@@ -486,7 +1056,7 @@ impl<T: HttpClient> ConnectedClient<T> {
 
         // Send request
         let response = self.http_client.post_request::<PollRequest, PollResponse>(
-            format!("http://{}/api/v1/poll", &self.connected_server.address),
+            self.connected_server.address.endpoint("/api/v1/poll"),
             request
         ).await?;
 
@@ -509,4 +1079,245 @@ impl<T: HttpClient> ConnectedClient<T> {
             }
         }
     }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(ret, skip_all, fields(
+        channel = channel.to_string(),
+        limit,
+        wait = wait.as_secs()
+    )))]
+    /// Poll (read and delete) messages from the server's inbox,
+    /// waiting for them if the inbox is empty.
+    /// 
+    /// This method will perform `POST /api/v1/poll` request
+    /// with the `wait` field set.
+    /// 
+    /// Server will hold the request until a message arrives
+    /// to the channel or the `wait` timeout expires, so
+    /// messages can be received without frequent polling.
+    /// Server may limit the timeout, or answer immediately
+    /// if it doesn't support long polling.
+    /// 
+    /// This method will return vector of polled messages and
+    /// amount of remaining messages in the server's inbox.
+    pub async fn poll_wait(&self, channel: impl ToString, limit: Option<u64>, wait: Duration) -> Result<(Vec<MessageInfo>, u64), Error> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Sending POST /api/v1/poll request");
+
+        // Prepare poll request
+        let request = PollRequest(Request::new(
+            self.driver.secret_key(),
+            PollRequestBody::new(channel, limit).with_wait(wait.as_secs())
+        ));
+
+        let proof_seed = request.0.proof_seed;
+
+        // Send request
+        let response = self.http_client.post_request::<PollRequest, PollResponse>(
+            self.connected_server.address.endpoint("/api/v1/poll"),
+            request
+        ).await?;
+
+        // Validate response
+        if !response.validate(proof_seed)? {
+            return Err(Error::InvalidProofSeedSignature);
+        }
+
+        // Check response status
+        match response.0 {
+            Response::Success { response, .. } => {
+                Ok((response.messages, response.remaining))
+            }
+
+            Response::Error { status, reason, .. } => {
+                Err(Error::RequestFailed {
+                    status,
+                    reason
+                })
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(ret, skip_all, fields(
+        channel = channel.to_string(),
+        limit,
+        lease = lease.as_secs()
+    )))]
+    /// Lease messages from the server's inbox.
+    /// 
+    /// This method will perform `POST /api/v1/poll` request
+    /// with the `lease` field set.
+    /// 
+    /// Unlike `poll`, leased messages are not removed from
+    /// the server's inbox, but hidden for the `lease` duration.
+    /// They must be acknowledged using the `ack` method with
+    /// returned delivery ids, otherwise they will be returned
+    /// to the inbox and delivered again.
+    /// 
+    /// This method will return vector of leased messages with
+    /// their delivery ids and amount of remaining messages
+    /// in the server's inbox.
+    pub async fn poll_leased(&self, channel: impl ToString, limit: Option<u64>, lease: Duration) -> Result<(Vec<(u64, MessageInfo)>, u64), Error> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Sending POST /api/v1/poll request");
+
+        // Prepare poll request
+        let request = PollRequest::leased(self.driver.secret_key(), channel, limit, lease.as_secs());
+
+        let proof_seed = request.0.proof_seed;
+
+        // Send request
+        let response = self.http_client.post_request::<PollRequest, PollResponse>(
+            self.connected_server.address.endpoint("/api/v1/poll"),
+            request
+        ).await?;
+
+        // Validate response
+        if !response.validate(proof_seed)? {
+            return Err(Error::InvalidProofSeedSignature);
+        }
+
+        // Check response status
+        match response.0 {
+            Response::Success { response, .. } => {
+                let Some(delivery_ids) = response.delivery_ids else {
+                    return Err(Error::RequestFailed {
+                        status: ResponseStatus::ServerError,
+                        reason: String::from("Server didn't lease polled messages")
+                    });
+                };
+
+                Ok((delivery_ids.into_iter().zip(response.messages).collect(), response.remaining))
+            }
+
+            Response::Error { status, reason, .. } => {
+                Err(Error::RequestFailed {
+                    status,
+                    reason
+                })
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(ret, skip_all))]
+    /// Acknowledge messages leased by the `poll_leased` method,
+    /// removing them from the server's inbox.
+    /// 
+    /// This method will perform `POST /api/v1/ack` request.
+    /// 
+    /// This method will return amount of acknowledged messages.
+    /// Messages which leases were expired can't be acknowledged.
+    pub async fn ack(&self, delivery_ids: impl Into<Vec<u64>>) -> Result<u64, Error> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Sending POST /api/v1/ack request");
+
+        // Prepare ack request
+        let request = AckRequest::new(self.driver.secret_key(), delivery_ids);
+
+        let proof_seed = request.0.proof_seed;
+
+        // Send request
+        let response = self.http_client.post_request::<AckRequest, AckResponse>(
+            self.connected_server.address.endpoint("/api/v1/ack"),
+            request
+        ).await?;
+
+        // Validate response
+        if !response.validate(proof_seed)? {
+            return Err(Error::InvalidProofSeedSignature);
+        }
+
+        // Check response status
+        match response.0 {
+            Response::Success { response, .. } => {
+                Ok(response.acknowledged)
+            }
+
+            Response::Error { status, reason, .. } => {
+                Err(Error::RequestFailed {
+                    status,
+                    reason
+                })
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    /// Subscribe to the new messages of the server's inbox.
+    /// 
+    /// This method will open `GET /api/v1/subscribe` WebSocket
+    /// connection and sign the server's challenge. Once accepted,
+    /// the server pushes messages sent to the given channels
+    /// as soon as they arrive. Pushed messages are removed
+    /// from the server's inbox.
+    /// 
+    /// Returned stream ends when the connection is closed.
+    /// HTTP client must support WebSockets.
+    pub async fn subscribe(&self, channels: impl IntoIterator<Item = impl ToString>) -> Result<impl futures::Stream<Item = Result<MessageInfo, Error>> + Send, Error> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Opening GET /api/v1/subscribe connection");
+
+        let mut socket = self.http_client.websocket(
+            self.connected_server.address.endpoint("/api/v1/subscribe")
+        ).await?;
+
+        // Receive the server's challenge
+        let challenge = match socket.recv().await {
+            Some(challenge) => SubscribeChallenge::from_json(&challenge?)
+                .map_err(|err| Error::Other(err.into()))?,
+
+            None => return Err(Error::Other("Connection closed by the server".into()))
+        };
+
+        // Prepare subscribe request
+        let request = SubscribeRequest::new(self.driver.secret_key(), challenge.challenge, channels);
+
+        let proof_seed = request.0.proof_seed;
+
+        // Send request
+        let request = request.to_json()
+            .map_err(|err| Error::Other(err.into()))?;
+
+        socket.send(request).await?;
+
+        let response = match socket.recv().await {
+            Some(response) => SubscribeResponse::from_json(&response?)
+                .map_err(|err| Error::Other(err.into()))?,
+
+            None => return Err(Error::Other("Connection closed by the server".into()))
+        };
+
+        // Validate response
+        if !response.validate(proof_seed)? {
+            return Err(Error::InvalidProofSeedSignature);
+        }
+
+        // Check response status
+        if let Response::Error { status, reason, .. } = response.0 {
+            return Err(Error::RequestFailed {
+                status,
+                reason
+            });
+        }
+
+        let stream = futures::stream::unfold((Some(socket), VecDeque::new()), |(mut socket, mut messages)| async move {
+            loop {
+                if let Some(message) = messages.pop_front() {
+                    return Some((Ok(message), (socket, messages)));
+                }
+
+                match socket.as_mut()?.recv().await? {
+                    Ok(update) => match SubscribeUpdate::from_json(&update) {
+                        Ok(update) => messages.extend(update.messages),
+
+                        Err(err) => return Some((Err(Error::Other(err.into())), (socket, messages)))
+                    }
+
+                    // Stop the stream after connection errors
+                    Err(err) => return Some((Err(Error::Other(err)), (None, messages)))
+                }
+            }
+        });
+
+        Ok(stream)
+    }
 }
@@ -0,0 +1,178 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use moka::sync::Cache;
+
+use crate::crypto::asymmetric::PublicKey;
+
+use crate::rest_api::prelude::{
+    Client as ClientApiRecord,
+    Server as ServerApiRecord,
+    ClientType,
+    ServerAddress
+};
+
+type LookupCacheKey = (PublicKey, Option<ClientType>);
+type LookupCacheValue = (ClientApiRecord, ServerApiRecord, bool);
+
+/// Default maximal amount of cached lookup results.
+pub const DEFAULT_LOOKUP_CACHE_CAPACITY: u64 = 4096;
+
+#[derive(Debug)]
+/// Cache of the client-side lookup results.
+/// 
+/// Used by the `ConnectedClient` middleware to not to
+/// resolve the same clients over the network every time.
+/// Entries are removed when their TTL expires, when the
+/// cache is full, or when the cached server reports that
+/// the client is not connected to it anymore.
+pub struct LookupCache {
+    ttl: Duration,
+    records: Cache<LookupCacheKey, LookupCacheValue>,
+    hits: AtomicU64,
+    misses: AtomicU64
+}
+
+impl LookupCache {
+    #[inline]
+    /// Create new lookup cache with given TTL of the entries
+    /// and `DEFAULT_LOOKUP_CACHE_CAPACITY` capacity.
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_capacity(ttl, DEFAULT_LOOKUP_CACHE_CAPACITY)
+    }
+
+    /// Create new lookup cache with given TTL of the entries
+    /// and maximal amount of cached results.
+    pub fn with_capacity(ttl: Duration, capacity: u64) -> Self {
+        Self {
+            ttl,
+            records: Cache::builder()
+                .time_to_live(ttl)
+                .max_capacity(capacity)
+                .support_invalidation_closures()
+                .build(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0)
+        }
+    }
+
+    #[inline]
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Get cached lookup result.
+    /// 
+    /// Updates hits and misses counters.
+    pub fn get(&self, client_public: &PublicKey, client_type: Option<ClientType>) -> Option<LookupCacheValue> {
+        let value = self.records.get(&(client_public.clone(), client_type));
+
+        if value.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+
+        value
+    }
+
+    #[inline]
+    /// Cache lookup result.
+    pub fn insert(&self, client_public: PublicKey, client_type: Option<ClientType>, value: LookupCacheValue) {
+        self.records.insert((client_public, client_type), value);
+    }
+
+    /// Remove cached results of the client connected
+    /// to the server with given address.
+    pub fn invalidate(&self, client_public: &PublicKey, server_address: &ServerAddress) {
+        let client_public = client_public.clone();
+        let server_address = server_address.clone();
+
+        self.records.invalidate_entries_if(move |(public_key, _), (_, server, _)| {
+            public_key == &client_public && server.address == server_address
+        }).expect("Lookup cache doesn't support invalidation closures");
+    }
+
+    #[inline]
+    /// Remove all the cached results.
+    pub fn clear(&self) {
+        self.records.invalidate_all();
+    }
+
+    /// Get amount of cached results.
+    pub fn len(&self) -> usize {
+        self.records.run_pending_tasks();
+
+        self.records.entry_count() as usize
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    /// Get amount of lookups resolved from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    /// Get amount of lookups not found in the cache.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypto::prelude::*;
+    use crate::rest_api::types::client::tests::get_client;
+
+    use super::*;
+
+    #[test]
+    fn cache() {
+        let client = get_client();
+        let server = ServerApiRecord::new(SecretKey::random().public_key(), "example.org".parse().unwrap());
+
+        let cache = LookupCache::new(Duration::from_secs(60));
+
+        assert_eq!(cache.get(&client.public_key, None), None);
+
+        cache.insert(client.public_key.clone(), None, (client.clone(), server.clone(), true));
+
+        assert_eq!(cache.get(&client.public_key, None), Some((client.clone(), server.clone(), true)));
+        assert_eq!(cache.get(&client.public_key, Some(ClientType::Thin)), None);
+
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 2);
+
+        // Other servers don't invalidate the entry
+        cache.invalidate(&client.public_key, &"example.com".parse().unwrap());
+
+        assert_eq!(cache.len(), 1);
+
+        cache.invalidate(&client.public_key, &server.address);
+
+        assert!(cache.is_empty());
+
+        // Expired entries are not returned
+        let cache = LookupCache::new(Duration::ZERO);
+
+        cache.insert(client.public_key.clone(), None, (client.clone(), server.clone(), true));
+
+        assert_eq!(cache.get(&client.public_key, None), None);
+
+        // Cache doesn't grow over its capacity
+        let cache = LookupCache::with_capacity(Duration::from_secs(60), 1);
+
+        for _ in 0..8 {
+            let client = get_client();
+
+            cache.insert(client.public_key.clone(), None, (client, server.clone(), true));
+        }
+
+        assert!(cache.len() <= 1);
+    }
+}
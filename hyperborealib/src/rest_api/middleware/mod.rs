@@ -4,9 +4,13 @@ use crate::rest_api::status::ResponseStatus;
 
 mod client;
 mod server;
+mod replay_guard;
+mod lookup_cache;
 
 pub use client::*;
 pub use server::*;
+pub use replay_guard::*;
+pub use lookup_cache::*;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -16,6 +20,9 @@ pub enum Error {
     #[error("Invalid proof seed signature")]
     InvalidProofSeedSignature,
 
+    #[error("Client lookup timed out")]
+    LookupTimeout,
+
     #[error(transparent)]
     CryptographyError(#[from] CryptographyError),
 
@@ -0,0 +1,254 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::time::timestamp;
+
+use crate::crypto::asymmetric::PublicKey;
+use crate::rest_api::request::Request;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum ReplayError {
+    #[error("Request is outdated. Timestamp: {timestamp}, current time: {current}")]
+    Outdated {
+        timestamp: u64,
+        current: u64
+    },
+
+    #[error("Request has no timestamp")]
+    NoTimestamp,
+
+    #[error("Request was already processed")]
+    Duplicate,
+
+    #[error("Too many requests were processed recently, try again later")]
+    Full
+}
+
+#[derive(Debug, Clone)]
+/// Protection from replay attacks.
+/// 
+/// Remembers proof seeds of the processed requests
+/// and rejects requests which were already processed
+/// or created too long ago (or too far in the future).
+/// 
+/// Seeds are stored for twice the requests lifetime,
+/// which covers all the timestamps accepted by the guard.
+/// At most `capacity` seeds are stored, so the memory used
+/// by the guard is bounded. Stored seeds are never evicted
+/// before they expire, so new requests are rejected with
+/// the `Full` error while the guard is full.
+/// 
+/// Requests without timestamp (first standard version)
+/// can't be checked for their age and could be replayed
+/// once their seeds are forgotten, so they're rejected
+/// unless allowed with `allow_untimed`.
+pub struct ReplayGuard {
+    lifetime: Duration,
+    allow_untimed: bool,
+    capacity: usize,
+    seeds: Arc<Mutex<ReplaySeeds>>
+}
+
+#[derive(Debug, Default)]
+/// Remembered seeds of the processed requests.
+struct ReplaySeeds {
+    seeds: HashSet<(PublicKey, u64)>,
+
+    /// Seeds in order of their expiration.
+    expirations: VecDeque<(Instant, (PublicKey, u64))>
+}
+
+impl Default for ReplayGuard {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Duration::from_secs(60 * 5), 256 * 1024)
+    }
+}
+
+impl ReplayGuard {
+    pub fn new(lifetime: Duration, capacity: u64) -> Self {
+        #[cfg(feature = "tracing")]
+        tracing::trace!("Building new ReplayGuard with {} seconds lifetime and {capacity} seeds capacity", lifetime.as_secs());
+
+        Self {
+            lifetime,
+            allow_untimed: false,
+            capacity: capacity as usize,
+            seeds: Arc::new(Mutex::new(ReplaySeeds::default()))
+        }
+    }
+
+    #[inline]
+    /// Accept requests without timestamp.
+    /// 
+    /// Only duplicates of such requests are rejected
+    /// while their seeds are remembered by the guard.
+    pub fn allow_untimed(mut self, allow: bool) -> Self {
+        self.allow_untimed = allow;
+
+        self
+    }
+
+    #[inline]
+    pub fn lifetime(&self) -> Duration {
+        self.lifetime
+    }
+
+    /// Verify that the request is not outdated
+    /// and was not processed before.
+    /// 
+    /// Request is remembered as processed if this
+    /// method returned `Ok`, so it must be called only
+    /// after the request's signature was validated.
+    /// Otherwise anybody could block future requests
+    /// by sending invalid requests with the same seeds.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use hyperborealib::crypto::prelude::*;
+    /// use hyperborealib::rest_api::prelude::*;
+    /// 
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let guard = ReplayGuard::default();
+    /// 
+    /// let request = Request::new(&SecretKey::random(), ());
+    /// 
+    /// assert!(guard.verify(&request).await.is_ok());
+    /// assert!(guard.verify(&request).await.is_err());
+    /// # });
+    /// ```
+    pub async fn verify<T>(&self, request: &Request<T>) -> Result<(), ReplayError> {
+        match request.proof_timestamp {
+            Some(request_timestamp) => {
+                let current = timestamp();
+                let lifetime = self.lifetime.as_secs();
+
+                if request_timestamp.saturating_add(lifetime) < current || request_timestamp > current.saturating_add(lifetime) {
+                    return Err(ReplayError::Outdated {
+                        timestamp: request_timestamp,
+                        current
+                    });
+                }
+            }
+
+            None if !self.allow_untimed => return Err(ReplayError::NoTimestamp),
+
+            None => ()
+        }
+
+        let mut seeds = self.seeds.lock()
+            .expect("Failed to lock replay guard");
+
+        // Forget expired seeds. All of them have the same
+        // lifetime, so they expire in the insertion order
+        let now = Instant::now();
+
+        while let Some((expires_at, _)) = seeds.expirations.front() {
+            if *expires_at > now {
+                break;
+            }
+
+            if let Some((_, seed)) = seeds.expirations.pop_front() {
+                seeds.seeds.remove(&seed);
+            }
+        }
+
+        let seed = (request.public_key.clone(), request.proof_seed);
+
+        if seeds.seeds.contains(&seed) {
+            return Err(ReplayError::Duplicate);
+        }
+
+        if seeds.seeds.len() >= self.capacity {
+            return Err(ReplayError::Full);
+        }
+
+        seeds.seeds.insert(seed.clone());
+        seeds.expirations.push_back((now + self.lifetime * 2, seed));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypto::prelude::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn verify() {
+        let guard = ReplayGuard::new(Duration::from_secs(60), 1024);
+        let secret_key = SecretKey::random();
+
+        // Fresh request
+
+        let request = Request::new(&secret_key, ());
+
+        assert!(guard.verify(&request).await.is_ok());
+
+        // Duplicate request
+
+        assert_eq!(guard.verify(&request).await, Err(ReplayError::Duplicate));
+
+        // Outdated request
+
+        let mut request = Request::new(&secret_key, ());
+
+        request.proof_timestamp = Some(timestamp() - 120);
+
+        assert!(matches!(guard.verify(&request).await, Err(ReplayError::Outdated { .. })));
+
+        // Request from the future
+
+        let mut request = Request::new(&secret_key, ());
+
+        request.proof_timestamp = Some(timestamp() + 120);
+
+        assert!(matches!(guard.verify(&request).await, Err(ReplayError::Outdated { .. })));
+
+        // Request without timestamp
+
+        let mut request = Request::new(&secret_key, ());
+
+        request.proof_timestamp = None;
+
+        assert_eq!(guard.verify(&request).await, Err(ReplayError::NoTimestamp));
+
+        let guard = guard.allow_untimed(true);
+
+        assert!(guard.verify(&request).await.is_ok());
+        assert_eq!(guard.verify(&request).await, Err(ReplayError::Duplicate));
+    }
+
+    #[tokio::test]
+    async fn full() {
+        let guard = ReplayGuard::new(Duration::from_secs(60), 16);
+        let secret_key = SecretKey::random();
+
+        let first = Request::new(&secret_key, ());
+
+        assert!(guard.verify(&first).await.is_ok());
+
+        // Flood the guard
+        for _ in 1..16 {
+            assert!(guard.verify(&Request::new(&SecretKey::random(), ())).await.is_ok());
+        }
+
+        // New requests are rejected, remembered ones are still duplicates
+        assert_eq!(guard.verify(&Request::new(&secret_key, ())).await, Err(ReplayError::Full));
+        assert_eq!(guard.verify(&first).await, Err(ReplayError::Duplicate));
+
+        // Expired seeds free the space
+        let guard = ReplayGuard::new(Duration::from_secs(1), 1);
+
+        assert!(guard.verify(&Request::new(&secret_key, ())).await.is_ok());
+        assert_eq!(guard.verify(&Request::new(&secret_key, ())).await, Err(ReplayError::Full));
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+
+        assert!(guard.verify(&Request::new(&secret_key, ())).await.is_ok());
+    }
+}
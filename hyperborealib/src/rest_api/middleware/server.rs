@@ -1,6 +1,9 @@
 use std::net::ToSocketAddrs;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::collections::HashSet;
 
+use crate::crypto::asymmetric::PublicKey;
 use crate::http::client::HttpClient;
 use crate::http::server::HttpServer;
 
@@ -8,6 +11,55 @@ use crate::drivers::server::prelude::*;
 
 use crate::rest_api::prelude::*;
 
+use futures::future::Either;
+use futures::StreamExt;
+
+use crate::rest_api::types::Server as ServerApiRecord;
+
+/// Lifetime of a single messages subscription before it is renewed.
+const SUBSCRIPTION_KEEPALIVE: Duration = Duration::from_secs(30);
+
+/// Lease timeout of the messages pushed to the subscribed clients.
+/// 
+/// Pushed messages are acknowledged right after they're written
+/// to the socket, so the lease only matters if writing fails.
+const SUBSCRIPTION_LEASE: Duration = Duration::from_secs(60);
+
+/// Maximal number of channels in a single subscription.
+const SUBSCRIPTION_MAX_CHANNELS: usize = 16;
+
+/// Maximal number of messages pushed
+/// to the subscribed client in a single update.
+const SUBSCRIPTION_UPDATE_SIZE: u64 = 64;
+
+/// Lifetime of the routing statistics
+/// returned by the `GET /api/v1/info`.
+const STATS_LIFETIME: Duration = Duration::from_secs(10);
+
+/// Maximal time to wait for the info
+/// of a probed announced server.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximal number of simultaneous requests
+/// sent during a recursive client lookup.
+const LOOKUP_CONCURRENCY: usize = 8;
+
+/// Maximal number of servers requested
+/// on a single layer of a recursive client lookup.
+const LOOKUP_LAYER_WIDTH: usize = 32;
+
+/// Maximal number of simultaneous requests sent
+/// to propagate a client's disconnection.
+const DISCONNECT_CONCURRENCY: usize = 8;
+
+/// Maximal number of servers to which
+/// a client's disconnection is propagated.
+const DISCONNECT_MAX_SERVERS: usize = 64;
+
+/// Maximal time to wait for a server
+/// to process the propagated disconnection.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Clone, Hash)]
 /// Server HTTP middleware
 /// 
@@ -22,8 +74,8 @@ pub struct Server<HttpClientExt, HttpServerExt, RouterExt, TraversalExt, Message
 impl<HttpClientExt, HttpServerExt, RouterExt, TraversalExt, MessagesInboxExt>
     Server<HttpClientExt, HttpServerExt, RouterExt, TraversalExt, MessagesInboxExt>
 where
-    HttpClientExt: HttpClient,
-    HttpServerExt: HttpServer,
+    HttpClientExt: HttpClient + 'static,
+    HttpServerExt: HttpServer + Send,
     RouterExt: Router + Send + Sync + 'static,
     TraversalExt: Traversal + Send + Sync + 'static,
     MessagesInboxExt: MessagesInbox + Send + Sync + 'static,
@@ -40,21 +92,59 @@ where
             router_type = std::any::type_name::<RouterExt>(),
             traversal_type = std::any::type_name::<TraversalExt>(),
             messages_inbox_type = std::any::type_name::<MessagesInboxExt>(),
-            server_address = server_driver.params().address,
+            server_address = %server_driver.params().address,
             server_secret = server_driver.params().secret_key.to_base64(),
             "Building server REST API middleware"
         );
 
+        // First standard requests have no timestamps
+        let replay_guard = ReplayGuard::new(
+            server_driver.params().request_lifetime,
+            server_driver.params().replay_guard_capacity
+        ).allow_untimed(server_driver.params().min_standard < 2);
+
         let driver = Arc::new(server_driver);
 
         http_server.get("/api/v1/info", {
             let driver = driver.clone();
+            let started_at = Instant::now();
 
-            |client_address| async move {
+            // Routing statistics are cached since anybody can request them
+            let stats_cache = moka::future::Cache::<(), (u64, u64, u64)>::builder()
+                .time_to_live(STATS_LIFETIME)
+                .build();
+
+            move |client_address| async move {
                 #[cfg(feature = "tracing")]
                 tracing::trace!(?client_address, "GET /api/v1/info");
 
-                InfoResponse::new(&driver.params().secret_key)
+                let router = driver.router();
+
+                let response = InfoResponse::new(&driver.params().secret_key);
+
+                let stats = stats_cache.try_get_with((), async {
+                    Ok::<_, RouterExt::Error>((
+                        router.local_clients_count().await?,
+                        router.servers_count().await?,
+                        router.remote_clients_count().await?
+                    ))
+                }).await;
+
+                match stats {
+                    Ok((clients, servers, table_size)) => response.with_stats(InfoStats {
+                        clients,
+                        servers,
+                        table_size,
+                        uptime: started_at.elapsed().as_secs()
+                    }),
+
+                    Err(_err) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::error!("GET /api/v1/info: failed to collect stats: {_err}");
+
+                        response
+                    }
+                }
             }
         }).await;
 
@@ -96,279 +186,1017 @@ where
 
         http_server.post::<ConnectRequest, ConnectResponse, _>("/api/v1/connect", {
             let driver = driver.clone();
+            let replay_guard = replay_guard.clone();
 
             |client_address, request: ConnectRequest| async move {
                 #[cfg(feature = "tracing")]
                 tracing::trace!(?client_address, "POST /api/v1/connect");
 
-                // Validate incoming request
-                let validated = match request.validate(&driver.params().secret_key.public_key()) {
-                    Ok(validated) => validated,
+                let standard = request.0.standard;
+
+                let response = async move {
+                    // Validate incoming request and reject
+                    // outdated or already processed ones
+                    if let Err(response) = verify_request(
+                        &request.0,
+                        request.validate(&driver.params().secret_key.public_key()),
+                        &replay_guard,
+                        driver.params().min_standard,
+                        ConnectResponse::error
+                    ).await {
+                        return response;
+                    }
 
-                    Err(err) => return ConnectResponse::error(
-                        ResponseStatus::ServerError,
-                        format!("Failed to validate request: {err}")
-                    )
-                };
+                    // Index client in the routing table
+                    let client = Client::new(
+                        request.0.public_key,
+                        request.0.request.certificate,
+                        request.0.request.client
+                    );
 
-                // Check if request is valid
-                if !validated {
-                    return ConnectResponse::error(
-                        ResponseStatus::RequestValidationFailed,
-                        "Request validation failed"
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(
+                        client_public = client.public_key.to_base64(),
+                        client_info = std::any::type_name_of_val(&client.info),
+                        "POST /api/v1/connect: indexing local client"
                     );
-                }
 
-                // Index client in the routing table
-                let client = Client::new(
-                    request.0.public_key,
-                    request.0.request.certificate,
-                    request.0.request.client
-                );
+                    match driver.router().index_local_client(client).await {
+                        Ok(true) => (),
+
+                        Ok(false) => return ConnectResponse::error(
+                            ResponseStatus::RequestValidationFailed,
+                            "Client's certificate is invalid or outdated"
+                        ),
+
+                        Err(err) => return ConnectResponse::error(
+                            ResponseStatus::ServerError,
+                            format!("Failed to index local client: {err}")
+                        )
+                    }
+
+                    ConnectResponse::success(
+                        ResponseStatus::Success,
+                        &driver.params().secret_key,
+                        request.0.proof_seed
+                    )
+                }.await;
+
+                // Answer using the same standard version as the request
+                ConnectResponse(response.0.with_standard(standard))
+            }
+        }).await;
+
+        http_server.post::<DisconnectRequest, DisconnectResponse, _>("/api/v1/disconnect", {
+            let driver = driver.clone();
+            let replay_guard = replay_guard.clone();
+            let http_client = http_client.clone();
 
+            |client_address, request: DisconnectRequest| async move {
                 #[cfg(feature = "tracing")]
-                tracing::trace!(
-                    client_public = client.public_key.to_base64(),
-                    client_info = std::any::type_name_of_val(&client.info),
-                    "POST /api/v1/connect: indexing local client"
-                );
-
-                if let Err(err) = driver.router().index_local_client(client).await {
-                    return ConnectResponse::error(
-                        ResponseStatus::ServerError,
-                        format!("Failed to index local client: {err}")
-                    );
-                }
+                tracing::trace!(?client_address, "POST /api/v1/disconnect");
+
+                let standard = request.0.standard;
+
+                let response = async move {
+                    // Validate incoming request and reject
+                    // outdated or already processed ones
+                    if let Err(response) = verify_request(
+                        &request.0,
+                        request.validate(),
+                        &replay_guard,
+                        driver.params().min_standard,
+                        DisconnectResponse::error
+                    ).await {
+                        return response;
+                    }
 
-                ConnectResponse::success(
-                    ResponseStatus::Success,
-                    &driver.params().secret_key,
-                    request.0.proof_seed
-                )
+                    let public_key = &request.0.public_key;
+                    let certificate = &request.0.request.certificate;
+
+                    // Remove the client if it's connected to this server
+                    // with the same certificate, so newer connections
+                    // are not closed by replayed requests
+                    match driver.router().lookup_local_client(public_key, None).await {
+                        Ok(Some((client, _))) if &client.certificate == certificate => {
+                            #[cfg(feature = "tracing")]
+                            tracing::trace!(
+                                client_public = public_key.to_base64(),
+                                return_inbox = request.0.request.return_inbox,
+                                "POST /api/v1/disconnect: removing local client"
+                            );
+
+                            if let Err(err) = driver.router().remove_local_client(public_key).await {
+                                return DisconnectResponse::error(
+                                    ResponseStatus::ServerError,
+                                    format!("Failed to remove local client: {err}")
+                                );
+                            }
+
+                            // Drop or return pending messages
+                            let mut messages = driver.messages_inbox()
+                                .remove_inbox(public_key.clone())
+                                .await;
+
+                            if !request.0.request.return_inbox {
+                                messages.clear();
+                            }
+
+                            // Propagate disconnection to the known servers
+                            // which received announcements of this client
+                            let server_public = driver.params().secret_key.public_key();
+
+                            let mut servers = Vec::new();
+
+                            for public_key in request.0.request.announced_to.iter().take(DISCONNECT_MAX_SERVERS) {
+                                if public_key == &server_public {
+                                    continue;
+                                }
+
+                                match driver.router().lookup_server(public_key).await {
+                                    Ok(Some((server, _))) => servers.push(server),
+                                    Ok(None) => (),
+
+                                    Err(_err) => {
+                                        #[cfg(feature = "tracing")]
+                                        tracing::warn!(err = _err.to_string(), "POST /api/v1/disconnect: failed to lookup announced server");
+                                    }
+                                }
+                            }
+
+                            // Don't make the client wait for other servers
+                            if !servers.is_empty() {
+                                tokio::spawn(propagate_disconnect(http_client.clone(), request.clone(), servers));
+                            }
+
+                            return DisconnectResponse::success(
+                                ResponseStatus::Success,
+                                &driver.params().secret_key,
+                                request.0.proof_seed,
+                                messages
+                            );
+                        }
+
+                        Err(err) => return DisconnectResponse::error(
+                            ResponseStatus::ServerError,
+                            format!("Failed to lookup local client: {err}")
+                        ),
+
+                        _ => ()
+                    }
+
+                    // Remove the client announced to this server
+                    match driver.router().lookup_remote_client(public_key, None).await {
+                        Ok(Some((client, _, _))) if &client.certificate == certificate => {
+                            #[cfg(feature = "tracing")]
+                            tracing::trace!(
+                                client_public = public_key.to_base64(),
+                                "POST /api/v1/disconnect: removing remote client"
+                            );
+
+                            if let Err(err) = driver.router().remove_remote_client(public_key).await {
+                                return DisconnectResponse::error(
+                                    ResponseStatus::ServerError,
+                                    format!("Failed to remove remote client: {err}")
+                                );
+                            }
+
+                            return DisconnectResponse::success(
+                                ResponseStatus::Success,
+                                &driver.params().secret_key,
+                                request.0.proof_seed,
+                                vec![]
+                            );
+                        }
+
+                        Err(err) => return DisconnectResponse::error(
+                            ResponseStatus::ServerError,
+                            format!("Failed to lookup remote client: {err}")
+                        ),
+
+                        _ => ()
+                    }
+
+                    DisconnectResponse::error(
+                        ResponseStatus::ClientNotConnected,
+                        "Client with given certificate is not known to this server"
+                    )
+                }.await;
+
+                // Answer using the same standard version as the request
+                DisconnectResponse(response.0.with_standard(standard))
             }
         }).await;
 
         http_server.post::<AnnounceRequest, AnnounceResponse, _>("/api/v1/announce", {
             let driver = driver.clone();
+            let replay_guard = replay_guard.clone();
+            let http_client = http_client.clone();
 
             |client_address, request: AnnounceRequest| async move {
                 #[cfg(feature = "tracing")]
                 tracing::trace!(?client_address, "POST /api/v1/announce");
 
-                // Validate incoming request
-                let validated = match request.validate() {
-                    Ok(validated) => validated,
-
-                    Err(err) => return AnnounceResponse::error(
-                        ResponseStatus::ServerError,
-                        format!("Failed to validate request: {err}")
-                    )
-                };
-
-                // Check if request is valid
-                if !validated {
-                    return AnnounceResponse::error(
-                        ResponseStatus::RequestValidationFailed,
-                        "Request validation failed"
-                    );
-                }
+                let standard = request.0.standard;
+
+                let response = async move {
+                    // Validate incoming request and reject
+                    // outdated or already processed ones
+                    if let Err(response) = verify_request(
+                        &request.0,
+                        request.validate(),
+                        &replay_guard,
+                        driver.params().min_standard,
+                        AnnounceResponse::error
+                    ).await {
+                        return response;
+                    }
 
-                // Index client in the routing table
-                match request.0.request {
-                    AnnounceRequestBody::Client { client, server } => {
-                        if let Err(err) = driver.router().index_remote_client(client, server).await {
-                            return AnnounceResponse::error(
-                                ResponseStatus::ServerError,
-                                format!("Failed to index remote client: {err}")
+                    // Verify that the announced server is available
+                    // on the specified address
+                    if driver.params().probe_announced_servers {
+                        let server = match &request.0.request {
+                            AnnounceRequestBody::Client { server, .. } |
+                            AnnounceRequestBody::Server { server } => server
+                        };
+
+                        // No need to probe the current server
+                        if server.public_key != driver.params().secret_key.public_key() {
+                            #[cfg(feature = "tracing")]
+                            tracing::trace!(
+                                server_public = server.public_key.to_base64(),
+                                server_address = %server.address,
+                                "POST /api/v1/announce: probing announced server"
                             );
+
+                            let response = tokio::time::timeout(
+                                PROBE_TIMEOUT,
+                                http_client.get_request::<InfoResponse>(server.address.endpoint("/api/v1/info"))
+                            ).await;
+
+                            let verified = match response {
+                                Ok(Ok(info)) => info.public_key == server.public_key && info.validate().unwrap_or(false),
+                                _ => false
+                            };
+
+                            if !verified {
+                                return AnnounceResponse::error(
+                                    ResponseStatus::RequestValidationFailed,
+                                    "Failed to verify announced server"
+                                );
+                            }
                         }
                     }
 
-                    AnnounceRequestBody::Server { server } => {
-                        if let Err(err) = driver.router().index_server(server).await {
-                            return AnnounceResponse::error(
-                                ResponseStatus::ServerError,
-                                format!("Failed to index server: {err}")
-                            );
+                    // Index client in the routing table
+                    match request.0.request {
+                        AnnounceRequestBody::Client { client, server } => {
+                            match driver.router().index_remote_client(client, server).await {
+                                Ok(true) => (),
+
+                                Ok(false) => return AnnounceResponse::error(
+                                    ResponseStatus::RequestValidationFailed,
+                                    "Client's certificate is invalid or outdated"
+                                ),
+
+                                Err(err) => return AnnounceResponse::error(
+                                    ResponseStatus::ServerError,
+                                    format!("Failed to index remote client: {err}")
+                                )
+                            }
+                        }
+
+                        AnnounceRequestBody::Server { server } => {
+                            if let Err(err) = driver.router().index_server(server).await {
+                                return AnnounceResponse::error(
+                                    ResponseStatus::ServerError,
+                                    format!("Failed to index server: {err}")
+                                );
+                            }
                         }
                     }
-                }
 
-                AnnounceResponse::success(
-                    ResponseStatus::Success,
-                    &driver.params().secret_key,
-                    request.0.proof_seed
-                )
+                    AnnounceResponse::success(
+                        ResponseStatus::Success,
+                        &driver.params().secret_key,
+                        request.0.proof_seed
+                    )
+                }.await;
+
+                // Answer using the same standard version as the request
+                AnnounceResponse(response.0.with_standard(standard))
             }
         }).await;
 
         http_server.post::<LookupRequest, LookupResponse, _>("/api/v1/lookup", {
             let driver = driver.clone();
+            let replay_guard = replay_guard.clone();
+            let http_client = http_client.clone();
 
             |client_address, request: LookupRequest| async move {
                 #[cfg(feature = "tracing")]
                 tracing::trace!(?client_address, "POST /api/v1/lookup");
 
-                // Validate incoming request
-                let validated = match request.validate() {
-                    Ok(validated) => validated,
+                let standard = request.0.standard;
+
+                let response = async move {
+                    // Validate incoming request and reject
+                    // outdated or already processed ones
+                    if let Err(response) = verify_request(
+                        &request.0,
+                        request.validate(),
+                        &replay_guard,
+                        driver.params().min_standard,
+                        LookupResponse::error
+                    ).await {
+                        return response;
+                    }
 
-                    Err(err) => return LookupResponse::error(
-                        ResponseStatus::ServerError,
-                        format!("Failed to validate request: {err}")
-                    )
-                };
+                    // Try to find the client in the local index
+                    match driver.router().lookup_local_client(&request.0.request.public_key, request.0.request.client_type).await {
+                        Ok(Some((client, available))) => {
+                            let body = LookupResponseBody::local(client, available);
 
-                // Check if request is valid
-                if !validated {
-                    return LookupResponse::error(
-                        ResponseStatus::RequestValidationFailed,
-                        "Request validation failed"
-                    );
-                }
+                            return LookupResponse::success(
+                                ResponseStatus::Success,
+                                &driver.params().secret_key,
+                                request.0.proof_seed,
+                                body
+                            );
+                        }
 
-                // Try to find the client in the local index
-                match driver.router().lookup_local_client(&request.0.public_key, request.0.request.client_type).await {
-                    Ok(Some((client, available))) => {
-                        let body = LookupResponseBody::local(client, available);
+                        Err(err) => return LookupResponse::error(
+                            ResponseStatus::ServerError,
+                            format!("Failed to lookup local client: {err}")
+                        ),
 
-                        return LookupResponse::success(
-                            ResponseStatus::Success,
-                            &driver.params().secret_key,
-                            request.0.proof_seed,
-                            body
-                        );
+                        _ => ()
                     }
 
-                    Err(err) => return LookupResponse::error(
-                        ResponseStatus::ServerError,
-                        format!("Failed to lookup local client: {err}")
-                    ),
+                    // Try to find the client in the remote index
+                    match driver.router().lookup_remote_client(&request.0.request.public_key, request.0.request.client_type).await {
+                        Ok(Some((client, server, available))) => {
+                            let body = LookupResponseBody::remote(client, server, available);
 
-                    _ => ()
-                }
+                            return LookupResponse::success(
+                                ResponseStatus::Success,
+                                &driver.params().secret_key,
+                                request.0.proof_seed,
+                                body
+                            );
+                        }
+
+                        Err(err) => return LookupResponse::error(
+                            ResponseStatus::ServerError,
+                            format!("Failed to lookup remote client: {err}")
+                        ),
 
-                // Try to find the client in the remote index
-                match driver.router().lookup_remote_client(&request.0.public_key, request.0.request.client_type).await {
-                    Ok(Some((client, server, available))) => {
-                        let body = LookupResponseBody::remote(client, server, available);
+                        _ => ()
+                    }
+
+                    // Return searching hint if neither local nor known remote record found
+                    let hint = driver.router()
+                        .lookup_remote_client_hint(&request.0.request.public_key, request.0.request.client_type)
+                        .await;
+
+                    let hint = match hint {
+                        Ok(hint) => hint,
+
+                        Err(err) => return LookupResponse::error(
+                            ResponseStatus::ServerError,
+                            format!("Failed to lookup remote client hint: {err}")
+                        )
+                    };
 
+                    if !request.0.request.recursive || driver.params().lookup_max_hops == 0 {
                         return LookupResponse::success(
                             ResponseStatus::Success,
                             &driver.params().secret_key,
                             request.0.proof_seed,
-                            body
+                            LookupResponseBody::hint(hint)
                         );
                     }
 
-                    Err(err) => return LookupResponse::error(
-                        ResponseStatus::ServerError,
-                        format!("Failed to lookup remote client: {err}")
-                    ),
-
-                    _ => ()
-                }
+                    // Chase the hints if recursive lookup was requested
+                    let result = lookup_recursive(
+                        &http_client,
+                        driver.params(),
+                        &request.0.request.public_key,
+                        request.0.request.client_type,
+                        hint
+                    ).await;
+
+                    match result {
+                        Ok((client, server, available)) => {
+                            // Cache found client in the routing table
+                            if let Err(_err) = driver.router().index_remote_client(client.clone(), server.clone()).await {
+                                #[cfg(feature = "tracing")]
+                                tracing::warn!(err = _err.to_string(), "POST /api/v1/lookup: failed to index found client");
+                            }
+
+                            LookupResponse::success(
+                                ResponseStatus::Success,
+                                &driver.params().secret_key,
+                                request.0.proof_seed,
+                                LookupResponseBody::remote(client, server, available)
+                            )
+                        }
 
-                // Return searching hint if neither local nor known remote record found
-                let hint = driver.router()
-                    .lookup_remote_client_hint(&request.0.public_key, request.0.request.client_type)
-                    .await;
+                        Err(ResponseStatus::ClientLookupTimeout) => LookupResponse::error(
+                            ResponseStatus::ClientLookupTimeout,
+                            "Client lookup timed out"
+                        ),
 
-                match hint {
-                    Ok(hint) => LookupResponse::success(
-                        ResponseStatus::Success,
-                        &driver.params().secret_key,
-                        request.0.proof_seed,
-                        LookupResponseBody::hint(hint)
-                    ),
+                        Err(status) => LookupResponse::error(
+                            status,
+                            "Client not found"
+                        )
+                    }
+                }.await;
 
-                    Err(err) => LookupResponse::error(
-                        ResponseStatus::ServerError,
-                        format!("Failed to lookup remote client hint: {err}")
-                    )
-                }
+                // Answer using the same standard version as the request
+                LookupResponse(response.0.with_standard(standard))
             }
         }).await;
 
         http_server.post::<SendRequest, SendResponse, _>("/api/v1/send", {
             let driver = driver.clone();
+            let replay_guard = replay_guard.clone();
+            let http_client = http_client.clone();
 
             |client_address, request: SendRequest| async move {
                 #[cfg(feature = "tracing")]
                 tracing::trace!(?client_address, "POST /api/v1/send");
 
-                // Validate incoming request
-                let validated = match request.validate() {
-                    Ok(validated) => validated,
+                let standard = request.0.standard;
+
+                let response = async move {
+                    // Validate incoming request and reject
+                    // outdated or already processed ones
+                    if let Err(response) = verify_request(
+                        &request.0,
+                        request.validate(),
+                        &replay_guard,
+                        driver.params().min_standard,
+                        SendResponse::error
+                    ).await {
+                        return response;
+                    }
+
+                    // Update last seen time of the sender
+                    touch_client(driver.router(), &request.0.public_key).await;
+
+                    // Check that the receiver is connected to this server
+                    let receiver = driver.router()
+                        .lookup_local_client(&request.0.request.receiver_public, None)
+                        .await;
+
+                    match receiver {
+                        Ok(Some(_)) => (),
+
+                        Ok(None) if driver.params().forward_messages => {
+                            let receiver = driver.router()
+                                .lookup_remote_client(&request.0.request.receiver_public, None)
+                                .await;
+
+                            return match receiver {
+                                Ok(Some((_, server, _))) => {
+                                    #[cfg(feature = "tracing")]
+                                    tracing::trace!(
+                                        receiver_public = request.0.request.receiver_public.to_base64(),
+                                        receiver_server = %server.address,
+                                        "POST /api/v1/send: forwarding message to the receiver's server"
+                                    );
+
+                                    // Forward the original request since it's signed by the sender.
+                                    // Forwarding loops are stopped by the replay guard
+                                    let proof_seed = request.0.proof_seed;
+
+                                    let response = http_client.post_request::<SendRequest, SendResponse>(
+                                        server.address.endpoint("/api/v1/send"),
+                                        request
+                                    ).await;
+
+                                    match response {
+                                        Ok(SendResponse(Response::Success { .. })) => SendResponse::success(
+                                            ResponseStatus::Success,
+                                            &driver.params().secret_key,
+                                            proof_seed
+                                        ),
+
+                                        Ok(SendResponse(Response::Error { status, reason, .. })) => {
+                                            SendResponse::error(status, reason)
+                                        }
+
+                                        Err(err) => SendResponse::error(
+                                            ResponseStatus::ServerError,
+                                            format!("Failed to forward message to the receiver's server: {err}")
+                                        )
+                                    }
+                                }
+
+                                Ok(None) => SendResponse::error(
+                                    ResponseStatus::ClientNotConnected,
+                                    "Receiver is not connected to this server"
+                                ),
+
+                                Err(err) => SendResponse::error(
+                                    ResponseStatus::ServerError,
+                                    format!("Failed to lookup remote client: {err}")
+                                )
+                            };
+                        }
+
+                        Ok(None) => return SendResponse::error(
+                            ResponseStatus::ClientNotConnected,
+                            "Receiver is not connected to this server"
+                        ),
+
+                        Err(err) => return SendResponse::error(
+                            ResponseStatus::ServerError,
+                            format!("Failed to lookup local client: {err}")
+                        )
+                    }
+
+                    // Add message to the inbox
+                    let result = driver.messages_inbox().add_message(
+                        request.0.request.sender,
+                        request.0.request.receiver_public,
+                        request.0.request.channel,
+                        request.0.request.message
+                    ).await;
+
+                    if let Err(err) = result {
+                        let status = match err {
+                            MessagesInboxError::InboxFull => ResponseStatus::ClientInboxFull,
+                            MessagesInboxError::MessageTooLarge { .. } => ResponseStatus::MessageTooLarge,
+                            MessagesInboxError::Other(_) => ResponseStatus::ServerError
+                        };
+
+                        return SendResponse::error(
+                            status,
+                            format!("Failed to add message to the inbox: {err}")
+                        );
+                    }
 
-                    Err(err) => return SendResponse::error(
-                        ResponseStatus::ServerError,
-                        format!("Failed to validate request: {err}")
+                    SendResponse::success(
+                        ResponseStatus::Success,
+                        &driver.params().secret_key,
+                        request.0.proof_seed
                     )
-                };
+                }.await;
 
-                // Check if request is valid
-                if !validated {
-                    return SendResponse::error(
-                        ResponseStatus::RequestValidationFailed,
-                        "Request validation failed"
-                    );
-                }
+                // Answer using the same standard version as the request
+                SendResponse(response.0.with_standard(standard))
+            }
+        }).await;
 
-                // Add message to the inbox
-                driver.messages_inbox().add_message(
-                    request.0.request.sender,
-                    request.0.request.receiver_public,
-                    request.0.request.channel,
-                    request.0.request.message
-                ).await;
-
-                SendResponse::success(
-                    ResponseStatus::Success,
-                    &driver.params().secret_key,
-                    request.0.proof_seed
-                )
+        http_server.post::<SendBatchRequest, SendBatchResponse, _>("/api/v1/send_batch", {
+            let driver = driver.clone();
+            let replay_guard = replay_guard.clone();
+
+            |client_address, request: SendBatchRequest| async move {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(?client_address, "POST /api/v1/send_batch");
+
+                let standard = request.0.standard;
+
+                let response = async move {
+                    // Reject too large batches before verifying their signatures
+                    let batch_size = request.0.request.messages.len() as u64;
+
+                    if batch_size > driver.params().max_batch_size {
+                        return SendBatchResponse::error(
+                            ResponseStatus::InvalidRequestStructure,
+                            format!("Batch has {batch_size} messages, limit is {}", driver.params().max_batch_size)
+                        );
+                    }
+
+                    // Validate incoming request and reject
+                    // outdated or already processed ones
+                    if let Err(response) = verify_request(
+                        &request.0,
+                        request.validate(),
+                        &replay_guard,
+                        driver.params().min_standard,
+                        SendBatchResponse::error
+                    ).await {
+                        return response;
+                    }
+
+                    // Update last seen time of the sender
+                    touch_client(driver.router(), &request.0.public_key).await;
+
+                    let mut results = Vec::with_capacity(request.0.request.messages.len());
+
+                    // Batched messages are never forwarded, even with enabled
+                    // `forward_messages`, since the request is signed as a whole
+                    // and can't be split between the receivers' servers
+                    for message in request.0.request.messages {
+                        // Check that the receiver is connected to this server
+                        let receiver = driver.router()
+                            .lookup_local_client(&message.receiver_public, None)
+                            .await;
+
+                        match receiver {
+                            Ok(Some(_)) => (),
+
+                            Ok(None) => {
+                                results.push(SendBatchResult::error(
+                                    ResponseStatus::ClientNotConnected,
+                                    "Receiver is not connected to this server"
+                                ));
+
+                                continue;
+                            }
+
+                            Err(err) => {
+                                results.push(SendBatchResult::error(
+                                    ResponseStatus::ServerError,
+                                    format!("Failed to lookup local client: {err}")
+                                ));
+
+                                continue;
+                            }
+                        }
+
+                        // Add message to the inbox
+                        let result = driver.messages_inbox().add_message(
+                            request.0.request.sender.clone(),
+                            message.receiver_public,
+                            message.channel,
+                            message.message
+                        ).await;
+
+                        match result {
+                            Ok(()) => results.push(SendBatchResult::success()),
+
+                            Err(err) => {
+                                let status = match err {
+                                    MessagesInboxError::InboxFull => ResponseStatus::ClientInboxFull,
+                                    MessagesInboxError::MessageTooLarge { .. } => ResponseStatus::MessageTooLarge,
+                                    MessagesInboxError::Other(_) => ResponseStatus::ServerError
+                                };
+
+                                results.push(SendBatchResult::error(
+                                    status,
+                                    format!("Failed to add message to the inbox: {err}")
+                                ));
+                            }
+                        }
+                    }
+
+                    SendBatchResponse::success(
+                        ResponseStatus::Success,
+                        &driver.params().secret_key,
+                        request.0.proof_seed,
+                        SendBatchResponseBody::new(results)
+                    )
+                }.await;
+
+                // Answer using the same standard version as the request
+                SendBatchResponse(response.0.with_standard(standard))
             }
         }).await;
 
         http_server.post::<PollRequest, PollResponse, _>("/api/v1/poll", {
             let driver = driver.clone();
+            let replay_guard = replay_guard.clone();
 
             |client_address, request: PollRequest| async move {
                 #[cfg(feature = "tracing")]
                 tracing::trace!(?client_address, "POST /api/v1/poll");
 
-                // Validate incoming request
-                let validated = match request.validate() {
-                    Ok(validated) => validated,
+                let standard = request.0.standard;
+
+                let response = async move {
+                    // Validate incoming request and reject
+                    // outdated or already processed ones
+                    if let Err(response) = verify_request(
+                        &request.0,
+                        request.validate(),
+                        &replay_guard,
+                        driver.params().min_standard,
+                        PollResponse::error
+                    ).await {
+                        return response;
+                    }
+
+                    // Update last seen time of the sender
+                    touch_client(driver.router(), &request.0.public_key).await;
+
+                    let poll = || async {
+                        match request.0.request.lease {
+                            // Lease messages from the inbox
+                            Some(lease) => {
+                                let lease = Duration::from_secs(lease)
+                                    .min(driver.params().max_lease);
+
+                                let (messages, remaining) = driver.messages_inbox().lease_messages(
+                                    request.0.public_key.clone(),
+                                    request.0.request.channel.clone(),
+                                    request.0.request.limit,
+                                    lease
+                                ).await;
+
+                                PollResponseBody::leased(messages, remaining)
+                            }
+
+                            // Poll messages from the inbox
+                            None => {
+                                let (messages, remaining) = driver.messages_inbox().poll_messages(
+                                    request.0.public_key.clone(),
+                                    request.0.request.channel.clone(),
+                                    request.0.request.limit
+                                ).await;
+
+                                PollResponseBody::new(messages, remaining)
+                            }
+                        }
+                    };
+
+                    // Subscribe to the new messages before polling
+                    // the inbox to not miss messages added in between
+                    let notification = request.0.request.wait
+                        .map(|wait| Duration::from_secs(wait).min(driver.params().max_poll_wait))
+                        .filter(|wait| !wait.is_zero())
+                        .and_then(|wait| {
+                            driver.messages_inbox().subscribe(
+                                &request.0.public_key,
+                                &request.0.request.channel,
+                                wait
+                            )
+                        });
+
+                    let mut body = poll().await;
+
+                    // Hold the request until a message arrives
+                    if body.messages.is_empty() {
+                        if let Some(notification) = notification {
+                            if notification.await {
+                                body = poll().await;
+                            }
+                        }
+                    }
+
+                    PollResponse::success(
+                        ResponseStatus::Success,
+                        &driver.params().secret_key,
+                        request.0.proof_seed,
+                        body
+                    )
+                }.await;
+
+                // Answer using the same standard version as the request
+                PollResponse(response.0.with_standard(standard))
+            }
+        }).await;
+
+        http_server.post::<AckRequest, AckResponse, _>("/api/v1/ack", {
+            let driver = driver.clone();
+            let replay_guard = replay_guard.clone();
+
+            |client_address, request: AckRequest| async move {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(?client_address, "POST /api/v1/ack");
+
+                let standard = request.0.standard;
+
+                let response = async move {
+                    // Validate incoming request and reject
+                    // outdated or already processed ones
+                    if let Err(response) = verify_request(
+                        &request.0,
+                        request.validate(),
+                        &replay_guard,
+                        driver.params().min_standard,
+                        AckResponse::error
+                    ).await {
+                        return response;
+                    }
 
-                    Err(err) => return PollResponse::error(
-                        ResponseStatus::ServerError,
-                        format!("Failed to validate request: {err}")
+                    // Update last seen time of the sender
+                    touch_client(driver.router(), &request.0.public_key).await;
+
+                    // Remove acknowledged messages from the inbox
+                    let acknowledged = driver.messages_inbox().ack_messages(
+                        request.0.public_key,
+                        request.0.request.delivery_ids
+                    ).await;
+
+                    AckResponse::success(
+                        ResponseStatus::Success,
+                        &driver.params().secret_key,
+                        request.0.proof_seed,
+                        acknowledged
                     )
+                }.await;
+
+                // Answer using the same standard version as the request
+                AckResponse(response.0.with_standard(standard))
+            }
+        }).await;
+
+        http_server.websocket("/api/v1/subscribe", {
+            let driver = driver.clone();
+            let replay_guard = replay_guard.clone();
+
+            |client_address, mut socket| async move {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(?client_address, "GET /api/v1/subscribe");
+
+                // Send random challenge which must be signed by the client
+                let challenge = SubscribeChallenge::random();
+
+                let Ok(challenge_json) = challenge.to_json() else {
+                    return;
                 };
 
-                // Check if request is valid
-                if !validated {
-                    return PollResponse::error(
-                        ResponseStatus::RequestValidationFailed,
-                        "Request validation failed"
-                    );
+                if socket.send(challenge_json).await.is_err() {
+                    return;
                 }
 
-                // Poll messages from the inbox
-                let (messages, remaining) = driver.messages_inbox().poll_messages(
-                    request.0.public_key,
-                    request.0.request.channel,
-                    request.0.request.limit
-                ).await;
-
-                PollResponse::success(
-                    ResponseStatus::Success,
-                    &driver.params().secret_key,
-                    request.0.proof_seed,
-                    PollResponseBody::new(messages, remaining)
-                )
+                // Receive subscription request
+                let request = match socket.recv().await {
+                    Some(Ok(request)) => SubscribeRequest::from_json(&request),
+                    _ => return
+                };
+
+                let request = match request {
+                    Ok(request) => request,
+
+                    Err(err) => {
+                        let response = SubscribeResponse::error(
+                            ResponseStatus::InvalidRequestStructure,
+                            format!("Failed to decode request: {err}")
+                        );
+
+                        if let Ok(response) = response.to_json() {
+                            let _ = socket.send(response).await;
+                        }
+
+                        let _ = socket.close().await;
+
+                        return;
+                    }
+                };
+
+                let standard = request.0.standard;
+
+                let response = async {
+                    // Validate incoming request and reject
+                    // outdated or already processed ones
+                    if let Err(response) = verify_request(
+                        &request.0,
+                        request.validate()
+                            .map(|validated| validated && request.0.request.challenge == challenge.challenge),
+                        &replay_guard,
+                        driver.params().min_standard,
+                        SubscribeResponse::error
+                    ).await {
+                        return response;
+                    }
+
+                    // Update last seen time of the sender
+                    touch_client(driver.router(), &request.0.public_key).await;
+
+                    let Some(channel) = request.0.request.channels.first() else {
+                        return SubscribeResponse::error(
+                            ResponseStatus::InvalidRequestStructure,
+                            "No channels to subscribe"
+                        );
+                    };
+
+                    if request.0.request.channels.len() > SUBSCRIPTION_MAX_CHANNELS {
+                        return SubscribeResponse::error(
+                            ResponseStatus::InvalidRequestStructure,
+                            format!("Subscription has {} channels, limit is {SUBSCRIPTION_MAX_CHANNELS}", request.0.request.channels.len())
+                        );
+                    }
+
+                    // Check if the inbox can notify about new messages
+                    if driver.messages_inbox().subscribe(&request.0.public_key, channel, Duration::ZERO).is_none() {
+                        return SubscribeResponse::error(
+                            ResponseStatus::ServerError,
+                            "Messages inbox doesn't support subscriptions"
+                        );
+                    }
+
+                    SubscribeResponse::success(
+                        ResponseStatus::Success,
+                        &driver.params().secret_key,
+                        request.0.proof_seed,
+                        SubscribeResponseBody::new(request.0.request.channels.clone())
+                    )
+                }.await;
+
+                // Answer using the same standard version as the request
+                let response = SubscribeResponse(response.0.with_standard(standard));
+
+                let Ok(response_json) = response.to_json() else {
+                    return;
+                };
+
+                if socket.send(response_json).await.is_err() {
+                    return;
+                }
+
+                if !matches!(response.0, Response::Success { .. }) {
+                    let _ = socket.close().await;
+
+                    return;
+                }
+
+                let receiver = request.0.public_key;
+                let channels = request.0.request.channels;
+
+                'subscription: loop {
+                    // Subscribed client is alive while the connection is open
+                    touch_client(driver.router(), &receiver).await;
+
+                    // Subscribe to the new messages before leasing
+                    // them to not miss messages added in between
+                    let notifications = channels.iter()
+                        .filter_map(|channel| {
+                            driver.messages_inbox().subscribe(&receiver, channel, SUBSCRIPTION_KEEPALIVE)
+                        })
+                        .collect::<Vec<_>>();
+
+                    // Messages are leased, so they're returned to the inbox
+                    // if they couldn't be written to the socket. Written
+                    // messages are acknowledged without any confirmation
+                    // from the client, so they're lost if the connection
+                    // breaks before the client receives them, same as
+                    // with the polled messages
+                    loop {
+                        let mut messages = Vec::new();
+                        let mut delivery_ids = Vec::new();
+
+                        // Stored messages are pushed in bounded updates
+                        let mut has_more = false;
+
+                        for channel in &channels {
+                            let limit = SUBSCRIPTION_UPDATE_SIZE - messages.len() as u64;
+
+                            if limit == 0 {
+                                has_more = true;
+
+                                break;
+                            }
+
+                            let (leased, remaining) = driver.messages_inbox().lease_messages(
+                                receiver.clone(),
+                                channel.clone(),
+                                Some(limit),
+                                SUBSCRIPTION_LEASE
+                            ).await;
+
+                            has_more |= remaining > 0;
+
+                            for (delivery_id, message) in leased {
+                                delivery_ids.push(delivery_id);
+                                messages.push(message);
+                            }
+                        }
+
+                        if messages.is_empty() {
+                            break;
+                        }
+
+                        let Ok(update) = SubscribeUpdate::new(messages).to_json() else {
+                            break 'subscription;
+                        };
+
+                        if socket.send(update).await.is_err() {
+                            break 'subscription;
+                        }
+
+                        driver.messages_inbox().ack_messages(receiver.clone(), delivery_ids).await;
+
+                        if !has_more {
+                            break;
+                        }
+                    }
+
+                    // Wait for new messages or closed connection
+                    let notification = futures::future::select_all(notifications);
+
+                    match futures::future::select(notification, socket.recv()).await {
+                        // New message or keepalive timeout
+                        Either::Left(_) => (),
+
+                        // Messages sent by the client are ignored
+                        Either::Right((Some(Ok(_)), _)) => (),
+
+                        // Connection is closed
+                        Either::Right(_) => break 'subscription
+                    }
+                }
+
+                #[cfg(feature = "tracing")]
+                tracing::trace!(?client_address, "GET /api/v1/subscribe: connection closed");
+
+                let _ = socket.close().await;
             }
         }).await;
 
@@ -402,4 +1230,814 @@ where
 
         self.http_server.serve(address).await
     }
+
+    #[inline]
+    /// Run HTTP REST API server on given Unix domain socket
+    pub async fn serve_unix(self, path: impl AsRef<std::path::Path> + Send) -> Result<(), Box<dyn std::error::Error>>
+    where
+        HttpServerExt: Send
+    {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Starting server on Unix domain socket");
+
+        self.http_server.serve_unix(path).await
+    }
+}
+
+/// Forward the client's disconnect request to the given servers.
+/// 
+/// Servers remove the client only if they store it with the same
+/// connection certificate, so the request is not forwarded any
+/// further. Failed and timed out requests are ignored since idle
+/// clients are eventually removed by the servers' routers.
+async fn propagate_disconnect(
+    http_client: impl HttpClient,
+    request: DisconnectRequest,
+    servers: Vec<ServerApiRecord>
+) {
+    let http_client = &http_client;
+    let request = &request;
+
+    futures::stream::iter(servers)
+        .map(|server| async move {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                client_public = request.0.public_key.to_base64(),
+                server_address = %server.address,
+                "POST /api/v1/disconnect: propagating disconnection"
+            );
+
+            let _response = tokio::time::timeout(
+                DISCONNECT_TIMEOUT,
+                http_client.post_request::<DisconnectRequest, DisconnectResponse>(
+                    server.address.endpoint("/api/v1/disconnect"),
+                    request.clone()
+                )
+            ).await;
+
+            #[cfg(feature = "tracing")]
+            match _response {
+                Ok(Ok(_)) => (),
+                Ok(Err(err)) => tracing::debug!(server_address = %server.address, "Failed to propagate disconnection: {err}"),
+                Err(_) => tracing::debug!(server_address = %server.address, "Failed to propagate disconnection: timeout")
+            }
+        })
+        .buffer_unordered(DISCONNECT_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+}
+
+/// Verify the incoming request before processing it.
+/// 
+/// Rejects requests which failed validation, use outdated
+/// standards, or were already processed. Returns the error
+/// response built with the `error` function otherwise.
+async fn verify_request<T, R>(
+    request: &Request<T>,
+    validated: Result<bool, ValidationError>,
+    replay_guard: &ReplayGuard,
+    min_standard: u64,
+    error: impl FnOnce(ResponseStatus, String) -> R
+) -> Result<(), R> {
+    match validated {
+        Ok(true) => (),
+
+        Ok(false) => return Err(error(
+            ResponseStatus::RequestValidationFailed,
+            String::from("Request validation failed")
+        )),
+
+        Err(err) => return Err(error(
+            ResponseStatus::ServerError,
+            format!("Failed to validate request: {err}")
+        ))
+    }
+
+    // Reject requests of outdated standards
+    if request.standard < min_standard {
+        return Err(error(
+            ResponseStatus::RequestValidationFailed,
+            format!("Standard {} is not supported, minimal is {min_standard}", request.standard)
+        ));
+    }
+
+    // Reject outdated and already processed requests
+    match replay_guard.verify(request).await {
+        Ok(()) => Ok(()),
+
+        // Request can be sent again once the guard has free space
+        Err(err @ ReplayError::Full) => Err(error(
+            ResponseStatus::ServerError,
+            err.to_string()
+        )),
+
+        Err(err) => Err(error(
+            ResponseStatus::RequestReplayed,
+            err.to_string()
+        ))
+    }
+}
+
+/// Update last seen time of the client which sent a signed request.
+/// 
+/// Errors are only logged to not reject the client's request.
+async fn touch_client(router: &(impl Router + Sync), public_key: &PublicKey) {
+    if let Err(_err) = router.touch_local_client(public_key).await {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            client = public_key.to_base64(),
+            err = _err.to_string(),
+            "Failed to update client's last seen time"
+        );
+    }
+}
+
+/// Find the client by chasing lookup hints of the given servers.
+/// 
+/// Servers are requested layer by layer, so the client is
+/// searched no further than `lookup_max_hops` servers away.
+/// Found clients' certificates are verified.
+/// 
+/// Every layer has no more than `LOOKUP_LAYER_WIDTH` servers,
+/// so hints returned by other servers can't make this server
+/// request the whole network.
+/// 
+/// Return `ClientNotFound` status if the client wasn't found,
+/// or `ClientLookupTimeout` if the lookup took longer than
+/// `lookup_timeout`.
+async fn lookup_recursive(
+    http_client: &impl HttpClient,
+    params: &ServerParams,
+    client_public: &PublicKey,
+    client_type: Option<ClientType>,
+    servers: Vec<ServerApiRecord>
+) -> Result<(Client, ServerApiRecord, bool), ResponseStatus> {
+    let deadline = tokio::time::Instant::now() + params.lookup_timeout;
+
+    // Store requested servers to prevent infinite lookup loops
+    let mut used_servers = HashSet::from([
+        params.secret_key.public_key()
+    ]);
+
+    let mut layer = Vec::new();
+
+    extend_lookup_layer(&mut layer, &mut used_servers, servers);
+
+    for _ in 0..params.lookup_max_hops {
+        if layer.is_empty() {
+            break;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(servers = layer.len(), "POST /api/v1/lookup: requesting hinted servers");
+
+        let request = LookupRequest::new(&params.secret_key, client_public.clone(), client_type);
+
+        let proof_seed = request.0.proof_seed;
+
+        let mut responses = futures::stream::iter(layer)
+            .map(|server| {
+                let request = request.clone();
+
+                async move {
+                    let response = http_client.post_request::<LookupRequest, LookupResponse>(
+                        server.address.endpoint("/api/v1/lookup"),
+                        request
+                    ).await;
+
+                    (server, response)
+                }
+            })
+            .buffer_unordered(LOOKUP_CONCURRENCY);
+
+        let mut next_layer = Vec::new();
+
+        loop {
+            let (server, response) = match tokio::time::timeout_at(deadline, responses.next()).await {
+                Ok(Some(response)) => response,
+                Ok(None) => break,
+
+                Err(_) => return Err(ResponseStatus::ClientLookupTimeout)
+            };
+
+            // Skip unavailable servers and invalid responses
+            let Ok(response) = response else {
+                continue;
+            };
+
+            if !response.validate(proof_seed).unwrap_or(false) {
+                continue;
+            }
+
+            let Response::Success { public_key, response, .. } = response.0 else {
+                continue;
+            };
+
+            let (client, server, available) = match response {
+                LookupResponseBody::Local { client, available } if public_key == server.public_key => {
+                    (client, server, available)
+                }
+
+                LookupResponseBody::Remote { client, server, available } => {
+                    (client, server, available)
+                }
+
+                LookupResponseBody::Hint { servers } => {
+                    extend_lookup_layer(&mut next_layer, &mut used_servers, servers);
+
+                    continue;
+                }
+
+                _ => continue
+            };
+
+            // Skip records with wrong or forged clients
+            let valid = &client.public_key == client_public &&
+                client.certificate.validate(&client.public_key, &server.public_key).unwrap_or(false);
+
+            if valid {
+                return Ok((client, server, available));
+            }
+        }
+
+        layer = next_layer;
+    }
+
+    Err(ResponseStatus::ClientNotFound)
+}
+
+/// Add not yet requested servers to the lookup layer,
+/// keeping it no wider than `LOOKUP_LAYER_WIDTH`.
+fn extend_lookup_layer(
+    layer: &mut Vec<ServerApiRecord>,
+    used_servers: &mut HashSet<PublicKey>,
+    servers: impl IntoIterator<Item = ServerApiRecord>
+) {
+    for server in servers {
+        if layer.len() >= LOOKUP_LAYER_WIDTH {
+            break;
+        }
+
+        if used_servers.insert(server.public_key.clone()) {
+            layer.push(server);
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "client-reqwest",
+    feature = "server-axum",
+    feature = "router-global-table",
+    feature = "traversal-bfs-recursion",
+    feature = "inbox-stored-queue"
+))]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::crypto::prelude::*;
+    use crate::http::{ReqwestHttpClient, AxumHttpServer};
+    use crate::drivers::ClientDriver;
+    use crate::rest_api::middleware::{Client, ConnectedClient, LookupParams, LookupCache, Error};
+
+    use super::*;
+
+    type ServerHandle = tokio::task::JoinHandle<Result<(), String>>;
+
+    /// Run test server with temporary router folder.
+    async fn run_server(folder: &std::path::Path) -> Result<(ServerAddress, ServerHandle), Box<dyn std::error::Error + Send + Sync>> {
+        run_server_with_params(folder, ServerParams::default()).await
+    }
+
+    /// Run test server with given params and temporary router folder.
+    async fn run_server_with_params(folder: &std::path::Path, params: ServerParams) -> Result<(ServerAddress, ServerHandle), Box<dyn std::error::Error + Send + Sync>> {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")?
+            .local_addr()?
+            .port();
+
+        let address = ServerAddress::new("http", "127.0.0.1", Some(port), "");
+
+        let driver = ServerDriver::new(
+            GlobalTableRouter::new(folder).await?,
+            BfsRecursionTraversal::default(),
+            StoredQueueMessagesInbox::default(),
+            ServerParams {
+                address: address.clone(),
+                ..params
+            }
+        );
+
+        let server = Server::new(
+            ReqwestHttpClient::default(),
+            AxumHttpServer::default(),
+            driver
+        ).await;
+
+        let server = tokio::spawn(async move {
+            server.serve(("127.0.0.1", port)).await
+                .map_err(|err| err.to_string())
+        });
+
+        Ok((address, server))
+    }
+
+    /// Connect new client to the test server, waiting until it starts.
+    async fn connect(address: &ServerAddress, secret_key: SecretKey) -> Result<ConnectedClient<ReqwestHttpClient>, Box<dyn std::error::Error + Send + Sync>> {
+        let client = Client::new(ReqwestHttpClient::default(), ClientDriver::new(ClientInfo::thin(), secret_key));
+
+        for _ in 0..50 {
+            if let Ok(client) = client.connect(address).await {
+                return Ok(client);
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        Ok(client.connect(address).await?)
+    }
+
+    #[tokio::test]
+    async fn min_standard() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-min-standard-test-{}", std::process::id()));
+
+        let (address, server) = run_server(&folder).await?;
+
+        // Wait until the server starts
+        connect(&address, SecretKey::random()).await?;
+
+        let client_secret = SecretKey::random();
+
+        let server_public = Client::new(ReqwestHttpClient::default(), ClientDriver::new(ClientInfo::thin(), client_secret.clone()))
+            .get_info(&address).await?
+            .public_key;
+
+        // First standard request which body is not signed
+        let mut request = ConnectRequest::new(&client_secret, server_public, ClientInfo::thin());
+
+        request.0.standard = 1;
+        request.0.proof_timestamp = None;
+        request.0.proof_sign = client_secret.create_signature(request.0.proof_seed.to_be_bytes());
+
+        let response = ReqwestHttpClient::default()
+            .post_request::<ConnectRequest, ConnectResponse>(address.endpoint("/api/v1/connect"), request)
+            .await?;
+
+        assert!(matches!(response.0, Response::Error { status: ResponseStatus::RequestValidationFailed, .. }));
+
+        server.abort();
+
+        std::fs::remove_dir_all(folder)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn send_batch() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let first_folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-send-batch-test-1-{}", std::process::id()));
+
+        let second_folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-send-batch-test-2-{}", std::process::id()));
+
+        let (first_address, first_server) = run_server(&first_folder).await?;
+        let (second_address, second_server) = run_server(&second_folder).await?;
+
+        let first_receiver = connect(&first_address, SecretKey::random()).await?;
+        let second_receiver = connect(&second_address, SecretKey::random()).await?;
+
+        let sender = connect(&first_address, SecretKey::random()).await?;
+
+        let message = Message::new("Hello, World!", "sign", MessageEncoding::from_str("base64")?);
+
+        let first_public = first_receiver.driver_ref().secret_key().public_key();
+        let second_public = second_receiver.driver_ref().secret_key().public_key();
+
+        let results = sender.send_batch([
+            (first_address.clone(), SendBatchMessage::new(first_public.clone(), "channel", message.clone())),
+            (second_address.clone(), SendBatchMessage::new(second_public.clone(), "channel", message.clone())),
+
+            // Receiver is not connected to this server
+            (first_address.clone(), SendBatchMessage::new(second_public, "channel", message.clone())),
+
+            (first_address.clone(), SendBatchMessage::new(first_public, "another channel", message.clone()))
+        ]).await;
+
+        assert_eq!(results.len(), 4);
+
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert!(results[3].is_ok());
+
+        assert!(matches!(results[2], Err(Error::RequestFailed { status: ResponseStatus::ClientNotConnected, .. })));
+
+        let (messages, _) = first_receiver.poll("channel", None).await?;
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].message, message);
+
+        let (messages, _) = first_receiver.poll("another channel", None).await?;
+
+        assert_eq!(messages.len(), 1);
+
+        let (messages, _) = second_receiver.poll("channel", None).await?;
+
+        assert_eq!(messages.len(), 1);
+
+        // Large groups are split into several batches
+        let first_public = first_receiver.driver_ref().secret_key().public_key();
+        let max_batch_size = ServerParams::default().max_batch_size;
+
+        let batch = (0..=max_batch_size).map(|_| {
+            (first_address.clone(), SendBatchMessage::new(first_public.clone(), "channel", message.clone()))
+        }).collect::<Vec<_>>();
+
+        let results = sender.send_batch(batch.clone()).await;
+
+        assert!(results.iter().all(Result::is_ok));
+
+        let (messages, _) = first_receiver.poll("channel", None).await?;
+
+        assert_eq!(messages.len() as u64, max_batch_size + 1);
+
+        // Too large batches are rejected entirely
+        let results = sender.clone()
+            .with_max_batch_size(max_batch_size + 1)
+            .send_batch(batch)
+            .await;
+
+        assert!(results.iter().all(|result| {
+            matches!(result, Err(Error::RequestFailed { status: ResponseStatus::InvalidRequestStructure, .. }))
+        }));
+
+        let (messages, _) = first_receiver.poll("channel", None).await?;
+
+        assert!(messages.is_empty());
+
+        first_server.abort();
+        second_server.abort();
+
+        std::fs::remove_dir_all(first_folder)?;
+        std::fs::remove_dir_all(second_folder)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn disconnect() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let first_folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-disconnect-test-1-{}", std::process::id()));
+
+        let second_folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-disconnect-test-2-{}", std::process::id()));
+
+        let (first_address, first_server) = run_server(&first_folder).await?;
+        let (second_address, second_server) = run_server(&second_folder).await?;
+
+        let receiver_secret = SecretKey::random();
+        let receiver_public = receiver_secret.public_key();
+
+        let receiver = connect(&first_address, receiver_secret.clone()).await?;
+        let sender = connect(&first_address, SecretKey::random()).await?;
+        let observer = connect(&second_address, SecretKey::random()).await?;
+
+        // Let the first server know about the second one,
+        // and the second server know about the receiver
+        let announcer = Client::new(ReqwestHttpClient::default(), ClientDriver::new(ClientInfo::thin(), SecretKey::random()));
+
+        announcer.announce(&first_address, AnnounceRequestBody::server(observer.connected_server().clone())).await?;
+
+        receiver.announce(&second_address).await?;
+
+        assert!(observer.lookup(receiver_public.clone(), None).await?.is_some());
+
+        let message = Message::new("Hello, World!", "sign", MessageEncoding::from_str("base64")?);
+
+        sender.send(&first_address, receiver_public.clone(), "channel", message.clone()).await?;
+
+        // Pending messages are returned
+        let messages = receiver.disconnect(true).await?;
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].message, message);
+
+        // Client is removed from both servers
+        let result = sender.send(&first_address, receiver_public.clone(), "channel", message.clone()).await;
+
+        assert!(matches!(result, Err(Error::RequestFailed { status: ResponseStatus::ClientNotConnected, .. })));
+
+        // Disconnection is propagated in background
+        let mut removed = false;
+
+        for _ in 0..50 {
+            if observer.lookup(receiver_public.clone(), None).await?.is_none() {
+                removed = true;
+
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        assert!(removed);
+
+        // Pending messages are dropped
+        let receiver = connect(&first_address, receiver_secret).await?;
+
+        sender.send(&first_address, receiver_public, "channel", message).await?;
+
+        assert!(receiver.disconnect(false).await?.is_empty());
+
+        first_server.abort();
+        second_server.abort();
+
+        std::fs::remove_dir_all(first_folder)?;
+        std::fs::remove_dir_all(second_folder)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lookup() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let first_folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-lookup-test-1-{}", std::process::id()));
+
+        let second_folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-lookup-test-2-{}", std::process::id()));
+
+        let (first_address, first_server) = run_server(&first_folder).await?;
+        let (second_address, second_server) = run_server(&second_folder).await?;
+
+        let requester = connect(&first_address, SecretKey::random()).await?;
+        let receiver = connect(&second_address, SecretKey::random()).await?;
+
+        let receiver_public = receiver.driver_ref().secret_key().public_key();
+
+        // Unavailable server
+        let unavailable_port = std::net::TcpListener::bind("127.0.0.1:0")?
+            .local_addr()?
+            .port();
+
+        let unavailable = ServerApiRecord::new(
+            SecretKey::random().public_key(),
+            ServerAddress::new("http", "127.0.0.1", Some(unavailable_port), "")
+        );
+
+        // Let the first server know about the unavailable and the second servers
+        let announcer = Client::new(ReqwestHttpClient::default(), ClientDriver::new(ClientInfo::thin(), SecretKey::random()));
+
+        announcer.announce(&first_address, AnnounceRequestBody::server(unavailable)).await?;
+        announcer.announce(&first_address, AnnounceRequestBody::server(receiver.connected_server().clone())).await?;
+
+        // Unavailable servers are skipped
+        let (client, server, _) = requester.lookup(receiver_public.clone(), None).await?
+            .expect("Client not found");
+
+        assert_eq!(client.public_key, receiver_public);
+        assert_eq!(&server, receiver.connected_server());
+
+        // Hinted servers are not requested without hops
+        let requester = requester.with_lookup_params(LookupParams {
+            max_hops: 0,
+            ..LookupParams::default()
+        });
+
+        assert!(requester.lookup(receiver_public.clone(), None).await?.is_none());
+
+        // Hinted servers are not requested without width
+        let requester = requester.with_lookup_params(LookupParams {
+            max_width: 0,
+            ..LookupParams::default()
+        });
+
+        assert!(requester.lookup(receiver_public, None).await?.is_none());
+
+        first_server.abort();
+        second_server.abort();
+
+        std::fs::remove_dir_all(first_folder)?;
+        std::fs::remove_dir_all(second_folder)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lookup_cache() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-lookup-cache-test-{}", std::process::id()));
+
+        let (address, server) = run_server(&folder).await?;
+
+        let receiver = connect(&address, SecretKey::random()).await?;
+
+        let requester = connect(&address, SecretKey::random()).await?
+            .with_lookup_cache(LookupCache::new(Duration::from_secs(60)));
+
+        let receiver_public = receiver.driver_ref().secret_key().public_key();
+
+        let record = requester.lookup(receiver_public.clone(), None).await?;
+        let cached = requester.lookup(receiver_public.clone(), None).await?;
+
+        assert!(record.is_some());
+        assert_eq!(record, cached);
+
+        let cache = requester.lookup_cache().unwrap();
+
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+
+        // Outdated record is removed when the server
+        // reports that the client is not connected
+        let (client, _, available) = record.unwrap();
+
+        let wrong_server = ServerApiRecord::new(
+            SecretKey::random().public_key(),
+            address.clone()
+        );
+
+        let unknown_public = SecretKey::random().public_key();
+
+        cache.insert(unknown_public.clone(), None, (client, wrong_server, available));
+
+        let message = Message::new("Hello, World!", "sign", MessageEncoding::from_str("base64")?);
+
+        let result = requester.send(&address, unknown_public.clone(), "channel", message).await;
+
+        assert!(matches!(result, Err(Error::RequestFailed { status: ResponseStatus::ClientNotConnected, .. })));
+        assert_eq!(cache.get(&unknown_public, None), None);
+
+        server.abort();
+
+        std::fs::remove_dir_all(folder)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lookup_recursive() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let first_folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-lookup-recursive-test-1-{}", std::process::id()));
+
+        let second_folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-lookup-recursive-test-2-{}", std::process::id()));
+
+        let (first_address, first_server) = run_server_with_params(&first_folder, ServerParams {
+            lookup_max_hops: 3,
+            ..ServerParams::default()
+        }).await?;
+        let (second_address, second_server) = run_server(&second_folder).await?;
+
+        let requester = connect(&first_address, SecretKey::random()).await?;
+        let receiver = connect(&second_address, SecretKey::random()).await?;
+
+        let receiver_public = receiver.driver_ref().secret_key().public_key();
+
+        // Let the first server know about the second one
+        Client::new(ReqwestHttpClient::default(), ClientDriver::new(ClientInfo::thin(), SecretKey::random()))
+            .announce(&first_address, AnnounceRequestBody::server(receiver.connected_server().clone()))
+            .await?;
+
+        let (client, server, _) = requester.lookup_recursive(receiver_public.clone(), None).await?
+            .expect("Client not found");
+
+        assert_eq!(client.public_key, receiver_public);
+        assert_eq!(&server, receiver.connected_server());
+
+        // Unknown clients are not found
+        assert!(requester.lookup_recursive(SecretKey::random().public_key(), None).await?.is_none());
+
+        // Found client is cached by the first server
+        second_server.abort();
+
+        let (client, server, _) = requester.lookup(receiver_public.clone(), None).await?
+            .expect("Client not cached");
+
+        assert_eq!(client.public_key, receiver_public);
+        assert_eq!(&server, receiver.connected_server());
+
+        first_server.abort();
+
+        std::fs::remove_dir_all(first_folder)?;
+        std::fs::remove_dir_all(second_folder)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn lookup_layer_width() {
+        let servers = (0..LOOKUP_LAYER_WIDTH * 2)
+            .map(|_| crate::rest_api::types::server::tests::get_server())
+            .collect::<Vec<_>>();
+
+        let mut used_servers = HashSet::from([
+            servers[0].public_key.clone()
+        ]);
+
+        let mut layer = Vec::new();
+
+        extend_lookup_layer(&mut layer, &mut used_servers, servers.clone());
+
+        assert_eq!(layer.len(), LOOKUP_LAYER_WIDTH);
+        assert_eq!(layer[0], servers[1]);
+
+        // Already requested servers are not added again
+        let mut next_layer = Vec::new();
+
+        extend_lookup_layer(&mut next_layer, &mut used_servers, layer);
+
+        assert!(next_layer.is_empty());
+    }
+
+    #[cfg(all(feature = "client-reqwest-ws", feature = "server-axum-ws"))]
+    #[tokio::test]
+    async fn subscribe() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-subscribe-test-{}", std::process::id()));
+
+        let (address, server) = run_server(&folder).await?;
+
+        let receiver_secret = SecretKey::random();
+
+        let receiver = connect(&address, receiver_secret.clone()).await?;
+        let sender = connect(&address, SecretKey::random()).await?;
+
+        let messages = receiver.subscribe(["channel"]).await?;
+
+        futures::pin_mut!(messages);
+
+        for text in ["Hello", "World"] {
+            let message = Message::new(text, "sign", MessageEncoding::from_str("base64")?);
+
+            sender.send(&address, receiver_secret.public_key(), "channel", message.clone()).await?;
+
+            // Messages of other channels are not pushed
+            sender.send(&address, receiver_secret.public_key(), "another channel", message.clone()).await?;
+
+            let info = tokio::time::timeout(Duration::from_secs(5), messages.next()).await?
+                .expect("Subscription closed")?;
+
+            assert_eq!(info.channel, "channel");
+            assert_eq!(info.message, message);
+        }
+
+        // Pushed messages are removed from the inbox
+        let (polled, _) = receiver.poll("channel", None).await?;
+
+        assert!(polled.is_empty());
+
+        let (polled, _) = receiver.poll("another channel", None).await?;
+
+        assert_eq!(polled.len(), 2);
+
+        server.abort();
+
+        std::fs::remove_dir_all(folder)?;
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "client-reqwest-ws", feature = "server-axum-ws"))]
+    #[tokio::test]
+    async fn subscribe_backlog() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let folder = std::env::temp_dir()
+            .join(format!(".hyperborealib-subscribe-backlog-test-{}", std::process::id()));
+
+        let (address, server) = run_server(&folder).await?;
+
+        let receiver_secret = SecretKey::random();
+
+        let receiver = connect(&address, receiver_secret.clone()).await?;
+        let sender = connect(&address, SecretKey::random()).await?;
+
+        // Subscriptions with too many channels are rejected
+        let channels = (0..=SUBSCRIPTION_MAX_CHANNELS).map(|i| format!("channel {i}"));
+
+        assert!(receiver.subscribe(channels).await.is_err());
+
+        // Stored messages are pushed in several updates
+        let total = SUBSCRIPTION_UPDATE_SIZE as usize * 2 + 1;
+
+        for i in 0..total {
+            let message = Message::new(format!("Message {i}"), "sign", MessageEncoding::from_str("base64")?);
+
+            sender.send(&address, receiver_secret.public_key(), "channel", message).await?;
+        }
+
+        let messages = receiver.subscribe(["channel"]).await?;
+
+        futures::pin_mut!(messages);
+
+        for i in 0..total {
+            let info = tokio::time::timeout(Duration::from_secs(5), messages.next()).await?
+                .expect("Subscription closed")?;
+
+            assert_eq!(info.message.content, format!("Message {i}"));
+        }
+
+        server.abort();
+
+        std::fs::remove_dir_all(folder)?;
+
+        Ok(())
+    }
 }
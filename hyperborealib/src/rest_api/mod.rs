@@ -2,6 +2,7 @@ use serde_json::Value as Json;
 
 use crate::crypto::Error as CryptographyError;
 
+pub mod canonical_json;
 pub mod request;
 pub mod response;
 pub mod status;
@@ -27,7 +28,11 @@ pub mod prelude {
         Client as ClientMiddleware,
         ConnectedClient as ConnectedClientMiddleware,
         Server as ServerMiddleware,
-        Error as MiddlewareError
+        Error as MiddlewareError,
+        LookupParams,
+        LookupCache,
+        ReplayGuard,
+        ReplayError
     };
 }
 
@@ -36,6 +41,9 @@ pub enum ValidationError {
     #[error("Proof seed must be a 64 bit long unsigned integer")]
     InvalidSeed,
 
+    #[error("Invalid standard version: {0}")]
+    InvalidStandard(u64),
+
     #[error(transparent)]
     CryptographyError(#[from] CryptographyError)
 }
@@ -1,9 +1,14 @@
 use serde_json::{json, Value as Json};
 
+use k256::sha2::{Sha256, Digest};
+
 use crate::crypto::prelude::*;
+use crate::time::timestamp;
 
 use crate::STANDARD_VERSION;
 
+use super::canonical_json::to_canonical_json;
+
 use super::{
     AsJson,
     AsJsonError,
@@ -19,21 +24,32 @@ use super::{
 /// (contain a proper header) which is used to verify
 /// that the request is sent by a client with specified
 /// public key.
+/// 
+/// Since the second standard version the proof signature
+/// covers the proof seed, the request's creation timestamp
+/// and the digest of the request's body, so the body can't
+/// be swapped, and the server can reject outdated and
+/// already processed requests.
+/// 
+/// First standard version requests are still readable.
+/// They have no timestamp and sign only the proof seed.
 pub struct Request<T> {
     pub standard: u64,
     pub public_key: PublicKey,
     pub proof_seed: u64,
+    pub proof_timestamp: Option<u64>,
     pub proof_sign: Vec<u8>,
     pub request: T
 }
 
-impl<T> Request<T> {
+impl<T: AsJson> Request<T> {
     /// Create new REST API request.
     /// 
     /// - `client_secret` must contain reference to
     ///   the secret key of the request's sender.
-    ///   It is used to sign random number to validate
-    ///   this request.
+    ///   It is used to sign random number, current
+    ///   timestamp and the request's body digest
+    ///   to validate this request.
     /// 
     /// - `request` can contain any value, preferably
     ///   implementing `AsJson` trait.
@@ -48,23 +64,51 @@ impl<T> Request<T> {
     /// ```
     pub fn new(client_secret: &SecretKey, request: T) -> Self {
         let proof_seed = safe_random_u64_long();
+        let proof_timestamp = timestamp();
 
-        let proof_sign = client_secret.create_signature(proof_seed.to_be_bytes());
+        let mut proof = Vec::with_capacity(48);
+
+        proof.extend_from_slice(&proof_seed.to_be_bytes());
+        proof.extend_from_slice(&proof_timestamp.to_be_bytes());
+        proof.extend_from_slice(&Self::body_digest(&request));
 
         Self {
             standard: STANDARD_VERSION,
             public_key: client_secret.public_key(),
             proof_seed,
-            proof_sign,
+            proof_timestamp: Some(proof_timestamp),
+            proof_sign: client_secret.create_signature(proof),
             request
         }
     }
 
+    /// Get SHA256 digest of the request's body.
+    /// 
+    /// Digest is calculated from the canonical JSON
+    /// representation of the body (see `to_canonical_json`).
+    pub fn body_digest(request: &T) -> [u8; 32] {
+        // Bodies which can't be serialized can't be sent either,
+        // so it's safe to use null value for them here
+        let body = request.to_json()
+            .unwrap_or(Json::Null);
+
+        Sha256::digest(to_canonical_json(&body)).into()
+    }
+
     /// Validate that the request's header is correct.
     /// 
     /// This method will verify that the proof signature
     /// is signed correctly by the sender using given public key.
     /// 
+    /// - Standard 1: signature of the proof seed.
+    /// 
+    /// - Standard 2: signature of the proof seed, timestamp
+    ///   and the request's body digest. Requests with modified
+    ///   body are not valid.
+    /// 
+    /// Request's timestamp is not verified here. Use
+    /// `ReplayGuard` to reject outdated requests.
+    /// 
     /// This method will also verify that the proof seed
     /// is correctly chosen (`>= 1^63`). This is important
     /// for signature generation to not to have many zero bytes.
@@ -88,10 +132,27 @@ impl<T> Request<T> {
             return Err(ValidationError::InvalidSeed);
         }
 
-        Ok(self.public_key.verify_signature(
-            self.proof_seed.to_be_bytes(),
-            &self.proof_sign
-        )?)
+        let proof = match self.standard {
+            1 => self.proof_seed.to_be_bytes().to_vec(),
+
+            2 => {
+                let Some(proof_timestamp) = self.proof_timestamp else {
+                    return Ok(false);
+                };
+
+                let mut proof = Vec::with_capacity(48);
+
+                proof.extend_from_slice(&self.proof_seed.to_be_bytes());
+                proof.extend_from_slice(&proof_timestamp.to_be_bytes());
+                proof.extend_from_slice(&Self::body_digest(&self.request));
+
+                proof
+            }
+
+            _ => return Err(ValidationError::InvalidStandard(self.standard))
+        };
+
+        Ok(self.public_key.verify_signature(proof, &self.proof_sign)?)
     }
 }
 
@@ -108,6 +169,23 @@ impl<T: AsJson> AsJson for Request<T> {
                 "request": self.request.to_json()?
             }),
 
+            2 => {
+                let Some(proof_timestamp) = self.proof_timestamp else {
+                    return Err(AsJsonError::FieldNotFound("proof.timestamp"));
+                };
+
+                json!({
+                    "standard": self.standard,
+                    "public_key": self.public_key.to_base64(),
+                    "proof": {
+                        "seed": self.proof_seed,
+                        "timestamp": proof_timestamp,
+                        "sign": base64_encode(&self.proof_sign)
+                    },
+                    "request": self.request.to_json()?
+                })
+            }
+
             _ => return Err(AsJsonError::InvalidStandard(self.standard))
         };
 
@@ -120,7 +198,7 @@ impl<T: AsJson> AsJson for Request<T> {
         };
 
         match standard {
-            1 => {
+            1 | 2 => {
                 let Some(public_key) = json.get("public_key").and_then(Json::as_str) else {
                     return Err(AsJsonError::FieldNotFound("public_key"));
                 };
@@ -133,6 +211,16 @@ impl<T: AsJson> AsJson for Request<T> {
                     return Err(AsJsonError::FieldNotFound("proof.seed"));
                 };
 
+                let proof_timestamp = if standard == 2 {
+                    let Some(proof_timestamp) = proof.get("timestamp").and_then(Json::as_u64) else {
+                        return Err(AsJsonError::FieldNotFound("proof.timestamp"));
+                    };
+
+                    Some(proof_timestamp)
+                } else {
+                    None
+                };
+
                 let Some(proof_sign) = proof.get("sign").and_then(Json::as_str) else {
                     return Err(AsJsonError::FieldNotFound("proof.sign"));
                 };
@@ -145,6 +233,7 @@ impl<T: AsJson> AsJson for Request<T> {
                     standard,
                     public_key: PublicKey::from_base64(public_key)?,
                     proof_seed,
+                    proof_timestamp,
                     proof_sign: base64_decode(proof_sign)?,
                     request: T::from_json(request)?
                 })
@@ -177,6 +266,15 @@ mod tests {
 
         assert_eq!(Request::from_json(&request.to_json()?)?, request);
 
+        // First standard request
+
+        let mut request = Request::new(&secret, ());
+
+        request.standard = 1;
+        request.proof_timestamp = None;
+
+        assert_eq!(Request::from_json(&request.to_json()?)?, request);
+
         Ok(())
     }
 
@@ -214,6 +312,48 @@ mod tests {
 
         assert!(request.validate().is_err());
 
+        // Invalid sign (different timestamp)
+
+        let mut request = Request::new(&secret_key, ());
+
+        request.proof_timestamp = request.proof_timestamp.map(|timestamp| timestamp - 1);
+
+        assert!(!request.validate()?);
+
+        // Invalid sign (different body)
+
+        let mut request = Request::new(&secret_key, String::from("Hello, World!"));
+
+        request.request = String::from("Goodbye, World!");
+
+        assert!(!request.validate()?);
+
+        // Invalid sign (no timestamp)
+
+        let mut request = Request::new(&secret_key, ());
+
+        request.proof_timestamp = None;
+
+        assert!(!request.validate()?);
+
+        // Valid first standard request header
+
+        let mut request = Request::new(&secret_key, String::from("Hello, World!"));
+
+        request.standard = 1;
+        request.proof_timestamp = None;
+        request.proof_sign = secret_key.create_signature(request.proof_seed.to_be_bytes());
+
+        assert!(request.validate()?);
+
+        // Invalid standard
+
+        let mut request = Request::new(&secret_key, ());
+
+        request.standard = 0;
+
+        assert!(request.validate().is_err());
+
         Ok(())
     }
 }
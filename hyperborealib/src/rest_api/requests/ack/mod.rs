@@ -0,0 +1,92 @@
+use serde_json::Value as Json;
+
+use crate::crypto::prelude::*;
+use crate::rest_api::prelude::*;
+
+mod request;
+mod response;
+
+pub use request::AckRequestBody;
+pub use response::AckResponseBody;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `POST /api/v1/ack` request.
+/// 
+/// This request is used to acknowledge messages leased
+/// by the `POST /api/v1/poll` request, so they can be
+/// removed from the server's inbox.
+/// 
+/// Leased messages which were not acknowledged will
+/// be returned to the inbox after their visibility timeout.
+pub struct AckRequest(pub Request<AckRequestBody>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `POST /api/v1/ack` response.
+pub struct AckResponse(pub Response<AckResponseBody>);
+
+impl AckRequest {
+    #[inline]
+    pub fn new(client_secret: &SecretKey, delivery_ids: impl Into<Vec<u64>>) -> Self {
+        Self(Request::new(client_secret, AckRequestBody::new(delivery_ids)))
+    }
+
+    #[inline]
+    /// Validate the request.
+    /// 
+    /// Calls `validate()` function on the request's body.
+    pub fn validate(&self) -> Result<bool, ValidationError> {
+        self.0.validate()
+    }
+}
+
+impl AsJson for AckRequest {
+    #[inline]
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        self.0.to_json()
+    }
+
+    #[inline]
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        Ok(Self(Request::from_json(json)?))
+    }
+}
+
+impl AckResponse {
+    pub fn success(status: ResponseStatus, server_secret: &SecretKey, proof_seed: u64, acknowledged: u64) -> Self {
+        let proof = server_secret.create_signature(proof_seed.to_be_bytes());
+
+        Self(Response::success(
+            status,
+            server_secret.public_key(),
+            proof,
+            AckResponseBody::new(acknowledged)
+        ))
+    }
+
+    #[inline]
+    pub fn error(status: ResponseStatus, reason: impl ToString) -> Self {
+        Self(Response::error(status, reason))
+    }
+
+    #[inline]
+    /// Validate the response.
+    /// 
+    /// Calls `validate()` function on the response's body.
+    pub fn validate(&self, proof_seed: u64) -> Result<bool, ValidationError> {
+        self.0.validate(proof_seed)
+    }
+}
+
+impl AsJson for AckResponse {
+    #[inline]
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        self.0.to_json()
+    }
+
+    #[inline]
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        Ok(Self(Response::from_json(json)?))
+    }
+}
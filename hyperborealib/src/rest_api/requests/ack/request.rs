@@ -0,0 +1,70 @@
+use serde_json::{json, Value as Json};
+
+use crate::rest_api::{AsJson, AsJsonError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `POST /api/v1/ack` request body.
+/// 
+/// Refer to `AckRequest` for details.
+pub struct AckRequestBody {
+    pub delivery_ids: Vec<u64>
+}
+
+impl AckRequestBody {
+    #[inline]
+    /// Create new `POST /api/v1/ack` request body.
+    /// 
+    /// - `delivery_ids` must be a list of delivery IDs
+    ///   of the leased messages returned by the
+    ///   `POST /api/v1/poll` request.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use hyperborealib::rest_api::prelude::*;
+    /// 
+    /// let request_body = AckRequestBody::new([1, 2, 3]);
+    /// ```
+    pub fn new(delivery_ids: impl Into<Vec<u64>>) -> Self {
+        Self {
+            delivery_ids: delivery_ids.into()
+        }
+    }
+}
+
+impl AsJson for AckRequestBody {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "delivery_ids": self.delivery_ids
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(delivery_ids) = json.get("delivery_ids").and_then(Json::as_array) else {
+            return Err(AsJsonError::FieldNotFound("delivery_ids"));
+        };
+
+        let Some(delivery_ids) = delivery_ids.iter().map(Json::as_u64).collect::<Option<Vec<_>>>() else {
+            return Err(AsJsonError::FieldValueInvalid("delivery_ids"));
+        };
+
+        Ok(Self {
+            delivery_ids
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() -> Result<(), AsJsonError> {
+        let request = AckRequestBody::new([1, 2, 3]);
+
+        assert_eq!(AckRequestBody::from_json(&request.to_json()?)?, request);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use serde_json::{json, Value as Json};
+
+use crate::rest_api::{AsJson, AsJsonError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `POST /api/v1/ack` response body.
+/// 
+/// Refer to `AckResponse` for details.
+pub struct AckResponseBody {
+    /// Amount of removed messages.
+    /// 
+    /// Messages which leases are expired can't
+    /// be acknowledged and are not counted.
+    pub acknowledged: u64
+}
+
+impl AckResponseBody {
+    #[inline]
+    pub fn new(acknowledged: u64) -> Self {
+        Self {
+            acknowledged
+        }
+    }
+}
+
+impl AsJson for AckResponseBody {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "acknowledged": self.acknowledged
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(acknowledged) = json.get("acknowledged").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("acknowledged"));
+        };
+
+        Ok(Self {
+            acknowledged
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() -> Result<(), AsJsonError> {
+        let response = AckResponseBody::new(3);
+
+        assert_eq!(AckResponseBody::from_json(&response.to_json()?)?, response);
+
+        Ok(())
+    }
+}
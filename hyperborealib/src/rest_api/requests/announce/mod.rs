@@ -59,11 +59,11 @@ impl AnnounceRequest {
     /// 
     /// Calls `validate()` function on the request's body
     /// and verifies that the provided connection certificate
-    /// is signed for the specified server.
+    /// is signed by the announced client for the announced server.
     /// 
-    /// - `server_public` must contain reference to the
-    ///   public key of the current server to which
-    ///   connection certificate was supposed to be signed.
+    /// Note that this method doesn't verify that the announced
+    /// server is really available on the specified address.
+    /// This can be done by requesting its `GET /api/v1/info`.
     pub fn validate(&self) -> Result<bool, ValidationError> {
         let mut valid_cert = true;
 
@@ -166,3 +166,33 @@ impl AsJson for AnnounceResponse {
         Ok(Self(Response::from_json(json)?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::rest_api::types::client::tests::get_client_for;
+    use crate::rest_api::types::server::tests::get_server;
+
+    use super::*;
+
+    #[test]
+    fn validate() -> Result<(), ValidationError> {
+        let secret = SecretKey::random();
+        let server = get_server();
+
+        let client = get_client_for(server.public_key.clone());
+
+        assert!(AnnounceRequest::client(&secret, client.clone(), server.clone()).validate()?);
+
+        // Certificate issued for another server
+        assert!(!AnnounceRequest::client(&secret, client.clone(), get_server()).validate()?);
+
+        // Certificate signed by another client
+        let mut spoofed = client;
+
+        spoofed.public_key = SecretKey::random().public_key();
+
+        assert!(!AnnounceRequest::client(&secret, spoofed, server).validate()?);
+
+        Ok(())
+    }
+}
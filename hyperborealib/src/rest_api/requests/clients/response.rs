@@ -64,7 +64,7 @@ impl ClientsResponse {
 impl AsJson for ClientsResponse {
     fn to_json(&self) -> Result<Json, AsJsonError> {
         match self.standard {
-            1 => Ok(json!({
+            1 | 2 => Ok(json!({
                 "standard": self.standard,
                 "clients": self.clients.iter()
                     .map(AsJson::to_json)
@@ -81,7 +81,7 @@ impl AsJson for ClientsResponse {
         };
 
         match standard {
-            1 => {
+            1 | 2 => {
                 let Some(clients) = json.get("clients").and_then(Json::as_array) else {
                     return Err(AsJsonError::FieldNotFound("clients"));
                 };
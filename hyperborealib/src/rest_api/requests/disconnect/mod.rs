@@ -0,0 +1,146 @@
+use serde_json::Value as Json;
+
+use crate::crypto::prelude::*;
+use crate::rest_api::prelude::*;
+
+mod request;
+mod response;
+
+pub use request::DisconnectRequestBody;
+pub use response::DisconnectResponseBody;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `POST /api/v1/disconnect` request.
+/// 
+/// This request is sent to the client's server to close
+/// the connection made by the `POST /api/v1/connect` request.
+/// The server removes the client from its routing table and
+/// drops or returns messages pending in the client's inbox.
+/// 
+/// The client's server forwards the same request to the known
+/// servers which received the client's announcements, so they
+/// remove the client's record as well. Only records with the
+/// same connection certificate are removed, so newer
+/// connections of the client are not affected.
+pub struct DisconnectRequest(pub Request<DisconnectRequestBody>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `POST /api/v1/disconnect` response.
+pub struct DisconnectResponse(pub Response<DisconnectResponseBody>);
+
+impl DisconnectRequest {
+    #[inline]
+    /// Craft new `POST /api/v1/disconnect` request.
+    /// 
+    /// - `client_secret` must contain reference to the
+    ///   client's secret key. It is used to sign the request.
+    /// 
+    /// - `certificate` must contain connection certificate
+    ///   of the connection which should be closed.
+    /// 
+    /// - `return_inbox` specifies whether pending messages
+    ///   should be returned in the response or dropped.
+    /// 
+    /// - `announced_to` must contain public keys of the servers
+    ///   which received the client's announcements.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use hyperborealib::rest_api::prelude::*;
+    /// use hyperborealib::crypto::prelude::*;
+    /// 
+    /// let client_secret = SecretKey::random();
+    /// let server_public = SecretKey::random().public_key();
+    /// 
+    /// let certificate = ConnectionCertificate::new(&client_secret, server_public);
+    /// 
+    /// let request = DisconnectRequest::new(&client_secret, certificate, false, []);
+    /// ```
+    pub fn new(client_secret: &SecretKey, certificate: ConnectionCertificate, return_inbox: bool, announced_to: impl Into<Vec<PublicKey>>) -> Self {
+        Self(Request::new(client_secret, DisconnectRequestBody::new(certificate, return_inbox, announced_to)))
+    }
+
+    #[inline]
+    /// Validate the request.
+    /// 
+    /// Calls `validate()` function on the request's body
+    /// and verifies that the provided connection certificate
+    /// is signed by the requesting client.
+    pub fn validate(&self) -> Result<bool, ValidationError> {
+        let certificate = &self.0.request.certificate;
+
+        Ok(self.0.validate()? && certificate.validate(&self.0.public_key, &certificate.token.public_key)?)
+    }
+}
+
+impl AsJson for DisconnectRequest {
+    #[inline]
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        self.0.to_json()
+    }
+
+    #[inline]
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        Ok(Self(Request::from_json(json)?))
+    }
+}
+
+impl DisconnectResponse {
+    /// Create successful `POST /api/v1/disconnect` response.
+    /// 
+    /// - `status` must contain status code of the response
+    ///   (`100 Success` in most cases).
+    /// 
+    /// - `server_secret` must contain reference to the
+    ///   secret key of the responding server. It is used
+    ///   to sign the response's proof.
+    /// 
+    /// - `proof_seed` must contain the same seed as used
+    ///   in the original request.
+    /// 
+    /// - `messages` must contain messages removed from
+    ///   the client's inbox if they were requested.
+    pub fn success(status: ResponseStatus, server_secret: &SecretKey, proof_seed: u64, messages: impl Into<Vec<MessageInfo>>) -> Self {
+        let proof = server_secret.create_signature(proof_seed.to_be_bytes());
+
+        Self(Response::success(
+            status,
+            server_secret.public_key(),
+            proof,
+            DisconnectResponseBody::new(messages)
+        ))
+    }
+
+    #[inline]
+    /// Create failed `POST /api/v1/disconnect` response.
+    /// 
+    /// - `status` must contain response's status.
+    /// 
+    /// - `reason` must contain error reason (message and/or description).
+    pub fn error(status: ResponseStatus, reason: impl ToString) -> Self {
+        Self(Response::error(status, reason))
+    }
+
+    #[inline]
+    /// Validate the response.
+    /// 
+    /// Calls `validate()` function on the response's body.
+    pub fn validate(&self, proof_seed: u64) -> Result<bool, ValidationError> {
+        self.0.validate(proof_seed)
+    }
+}
+
+impl AsJson for DisconnectResponse {
+    #[inline]
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        self.0.to_json()
+    }
+
+    #[inline]
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        Ok(Self(Response::from_json(json)?))
+    }
+}
@@ -0,0 +1,112 @@
+use serde_json::{json, Value as Json};
+
+use crate::crypto::prelude::*;
+use crate::rest_api::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `POST /api/v1/disconnect` request body.
+/// 
+/// Refer to the `DisconnectRequest` for details.
+pub struct DisconnectRequestBody {
+    /// Connection certificate of the closed connection.
+    pub certificate: ConnectionCertificate,
+
+    /// Return pending messages instead of dropping them.
+    pub return_inbox: bool,
+
+    /// Public keys of the servers which
+    /// received the client's announcements.
+    pub announced_to: Vec<PublicKey>
+}
+
+impl DisconnectRequestBody {
+    #[inline]
+    /// Create new `POST /api/v1/disconnect` request body.
+    /// 
+    /// - `certificate` must contain connection certificate
+    ///   of the connection which should be closed.
+    /// 
+    /// - `return_inbox` specifies whether pending messages
+    ///   should be returned in the response or dropped.
+    /// 
+    /// - `announced_to` must contain public keys of the servers
+    ///   which received the client's announcements, so the
+    ///   client's server can forward them this request.
+    pub fn new(certificate: ConnectionCertificate, return_inbox: bool, announced_to: impl Into<Vec<PublicKey>>) -> Self {
+        Self {
+            certificate,
+            return_inbox,
+            announced_to: announced_to.into()
+        }
+    }
+}
+
+impl AsJson for DisconnectRequestBody {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "certificate": self.certificate.to_json()?,
+            "return_inbox": self.return_inbox,
+            "announced_to": self.announced_to.iter()
+                .map(PublicKey::to_base64)
+                .collect::<Vec<_>>()
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(certificate) = json.get("certificate") else {
+            return Err(AsJsonError::FieldNotFound("certificate"));
+        };
+
+        // Field is optional, pending messages are dropped by default
+        let return_inbox = match json.get("return_inbox") {
+            Some(return_inbox) => return_inbox.as_bool()
+                .ok_or(AsJsonError::FieldValueInvalid("return_inbox"))?,
+
+            None => false
+        };
+
+        // Field is optional, the client could be not announced
+        let announced_to = match json.get("announced_to") {
+            Some(announced_to) => announced_to.as_array()
+                .ok_or(AsJsonError::FieldValueInvalid("announced_to"))?
+                .iter()
+                .map(|server| {
+                    server.as_str()
+                        .ok_or(AsJsonError::FieldValueInvalid("announced_to"))
+                        .map(PublicKey::from_base64)?
+                        .map_err(AsJsonError::from)
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+
+            None => vec![]
+        };
+
+        Ok(Self {
+            certificate: ConnectionCertificate::from_json(certificate)?,
+            return_inbox,
+            announced_to
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() -> Result<(), AsJsonError> {
+        let secret = SecretKey::random();
+        let public = SecretKey::random().public_key();
+
+        let request = DisconnectRequestBody::new(ConnectionCertificate::new(&secret, public.clone()), true, []);
+
+        assert_eq!(DisconnectRequestBody::from_json(&request.to_json()?)?, request);
+
+        let request = DisconnectRequestBody::new(ConnectionCertificate::new(&secret, public.clone()), false, [public]);
+
+        assert_eq!(DisconnectRequestBody::from_json(&request.to_json()?)?, request);
+
+        Ok(())
+    }
+}
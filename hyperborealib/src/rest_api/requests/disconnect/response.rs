@@ -0,0 +1,70 @@
+use serde_json::{json, Value as Json};
+
+use crate::rest_api::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `POST /api/v1/disconnect` response body.
+/// 
+/// Refer to `DisconnectResponse` for details.
+pub struct DisconnectResponseBody {
+    /// Messages removed from the client's inbox.
+    /// 
+    /// Empty if they were dropped.
+    pub messages: Vec<MessageInfo>
+}
+
+impl DisconnectResponseBody {
+    #[inline]
+    pub fn new(messages: impl Into<Vec<MessageInfo>>) -> Self {
+        Self {
+            messages: messages.into()
+        }
+    }
+}
+
+impl AsJson for DisconnectResponseBody {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "messages": self.messages.iter()
+                .map(MessageInfo::to_json)
+                .collect::<Result<Vec<_>, _>>()?
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(messages) = json.get("messages").and_then(Json::as_array) else {
+            return Err(AsJsonError::FieldNotFound("messages"));
+        };
+
+        Ok(Self {
+            messages: messages.iter()
+                .map(MessageInfo::from_json)
+                .collect::<Result<Vec<_>, _>>()?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::rest_api::types::sender::tests::get_sender;
+
+    use super::*;
+
+    #[test]
+    fn serialize() -> Result<(), AsJsonError> {
+        let response = DisconnectResponseBody::new(vec![]);
+
+        assert_eq!(DisconnectResponseBody::from_json(&response.to_json()?)?, response);
+
+        let encoding = MessageEncoding::from_str("base64").unwrap();
+        let message = Message::new("content", "sign", encoding);
+        let response = DisconnectResponseBody::new(vec![MessageInfo::now(get_sender(), "channel", message)]);
+
+        assert_eq!(DisconnectResponseBody::from_json(&response.to_json()?)?, response);
+
+        Ok(())
+    }
+}
@@ -1,3 +1,3 @@
 mod response;
 
-pub use response::InfoResponse;
+pub use response::{InfoResponse, InfoStats};
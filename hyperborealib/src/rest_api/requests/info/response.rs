@@ -20,7 +20,28 @@ pub struct InfoResponse {
     pub proof_seed: u64,
     pub proof_sign: Vec<u8>,
 
-    // TODO: stats
+    /// Optional server's statistics.
+    pub stats: Option<InfoStats>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Statistics of the server returned by the `GET /api/v1/info`.
+/// 
+/// They can be used by clients to choose lightly
+/// loaded servers to connect to.
+pub struct InfoStats {
+    /// Number of directly connected clients.
+    pub clients: u64,
+
+    /// Number of known servers.
+    pub servers: u64,
+
+    /// Size of the routing table (client -> server).
+    pub table_size: u64,
+
+    /// Number of seconds the server is online.
+    pub uptime: u64
 }
 
 impl InfoResponse {
@@ -47,10 +68,34 @@ impl InfoResponse {
             standard: STANDARD_VERSION,
             public_key: server_secret.public_key(),
             proof_seed,
-            proof_sign
+            proof_sign,
+            stats: None
         }
     }
 
+    #[inline]
+    /// Add server's statistics to the response.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use hyperborealib::crypto::prelude::*;
+    /// use hyperborealib::rest_api::prelude::*;
+    /// 
+    /// let response = InfoResponse::new(&SecretKey::random())
+    ///     .with_stats(InfoStats {
+    ///         clients: 10,
+    ///         servers: 5,
+    ///         table_size: 100,
+    ///         uptime: 3600
+    ///     });
+    /// ```
+    pub fn with_stats(mut self, stats: InfoStats) -> Self {
+        self.stats = Some(stats);
+
+        self
+    }
+
     /// Validate response proof.
     /// 
     /// # Example
@@ -78,16 +123,24 @@ impl InfoResponse {
 impl AsJson for InfoResponse {
     fn to_json(&self) -> Result<Json, AsJsonError> {
         match self.standard {
-            1 => Ok(json!({
-                "standard": self.standard,
-                "server": {
-                    "public_key": self.public_key.to_base64(),
-                },
-                "proof": {
-                    "seed": self.proof_seed,
-                    "sign": base64_encode(&self.proof_sign)
+            1 | 2 => {
+                let mut response = json!({
+                    "standard": self.standard,
+                    "server": {
+                        "public_key": self.public_key.to_base64(),
+                    },
+                    "proof": {
+                        "seed": self.proof_seed,
+                        "sign": base64_encode(&self.proof_sign)
+                    }
+                });
+
+                if let Some(stats) = &self.stats {
+                    response["stats"] = stats.to_json()?;
                 }
-            })),
+
+                Ok(response)
+            }
 
             _ => Err(AsJsonError::InvalidStandard(self.standard))
         }
@@ -99,7 +152,7 @@ impl AsJson for InfoResponse {
         };
 
         match standard {
-            1 => {
+            1 | 2 => {
                 let Some(server) = json.get("server") else {
                     return Err(AsJsonError::FieldNotFound("server"));
                 };
@@ -120,11 +173,17 @@ impl AsJson for InfoResponse {
                     return Err(AsJsonError::FieldNotFound("proof.sign"));
                 };
 
+                let stats = match json.get("stats") {
+                    Some(stats) => Some(InfoStats::from_json(stats)?),
+                    None => None
+                };
+
                 Ok(Self {
                     standard,
                     public_key: PublicKey::from_base64(public_key)?,
                     proof_seed,
-                    proof_sign: base64_decode(proof_sign)?
+                    proof_sign: base64_decode(proof_sign)?,
+                    stats
                 })
             }
 
@@ -133,6 +192,54 @@ impl AsJson for InfoResponse {
     }
 }
 
+impl AsJson for InfoStats {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "routing": {
+                "clients": self.clients,
+                "servers": self.servers,
+                "table_size": self.table_size
+            },
+            "service": {
+                "uptime": self.uptime
+            }
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(routing) = json.get("routing") else {
+            return Err(AsJsonError::FieldNotFound("stats.routing"));
+        };
+
+        let Some(service) = json.get("service") else {
+            return Err(AsJsonError::FieldNotFound("stats.service"));
+        };
+
+        let Some(clients) = routing.get("clients").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("stats.routing.clients"));
+        };
+
+        let Some(servers) = routing.get("servers").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("stats.routing.servers"));
+        };
+
+        let Some(table_size) = routing.get("table_size").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("stats.routing.table_size"));
+        };
+
+        let Some(uptime) = service.get("uptime").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("stats.service.uptime"));
+        };
+
+        Ok(Self {
+            clients,
+            servers,
+            table_size,
+            uptime
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,6 +250,15 @@ mod tests {
 
         assert_eq!(InfoResponse::from_json(&response.to_json()?)?, response);
 
+        let response = response.with_stats(InfoStats {
+            clients: 1,
+            servers: 2,
+            table_size: 3,
+            uptime: 4
+        });
+
+        assert_eq!(InfoResponse::from_json(&response.to_json()?)?, response);
+
         Ok(())
     }
 }
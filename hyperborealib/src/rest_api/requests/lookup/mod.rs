@@ -99,9 +99,9 @@ impl LookupResponse {
     /// use hyperborealib::rest_api::prelude::*;
     /// 
     /// let response_body = LookupResponseBody::hint(vec![
-    ///     Server::new(SecretKey::random().public_key(), "example1.org"),
-    ///     Server::new(SecretKey::random().public_key(), "example2.org"),
-    ///     Server::new(SecretKey::random().public_key(), "example3.org")
+    ///     Server::new(SecretKey::random().public_key(), "example1.org".parse().unwrap()),
+    ///     Server::new(SecretKey::random().public_key(), "example2.org".parse().unwrap()),
+    ///     Server::new(SecretKey::random().public_key(), "example3.org".parse().unwrap())
     /// ]);
     /// 
     /// let response = LookupResponse::success(
@@ -12,7 +12,15 @@ use crate::rest_api::prelude::*;
 /// Refer to `LookupRequest` for details.
 pub struct LookupRequestBody {
     pub public_key: PublicKey,
-    pub client_type: Option<ClientType>
+    pub client_type: Option<ClientType>,
+
+    /// Ask the server to chase lookup hints itself.
+    /// 
+    /// If set, then the server will never return
+    /// the `Hint` response, and will answer with
+    /// `ClientNotFound` or `ClientLookupTimeout`
+    /// statuses instead. Servers can ignore this field.
+    pub recursive: bool
 }
 
 impl LookupRequestBody {
@@ -39,17 +47,48 @@ impl LookupRequestBody {
     pub fn new(client_public: PublicKey, client_type: Option<ClientType>) -> Self {
         Self {
             public_key: client_public,
-            client_type
+            client_type,
+            recursive: false
         }
     }
+
+    #[inline]
+    /// Ask the server to perform recursive lookup.
+    /// 
+    /// Server will request the hinted servers itself
+    /// and return the found client, or an error
+    /// status if the client wasn't found.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use hyperborealib::crypto::prelude::*;
+    /// use hyperborealib::rest_api::prelude::*;
+    /// 
+    /// let client_public = SecretKey::random().public_key();
+    /// 
+    /// let request_body = LookupRequestBody::new(client_public, None)
+    ///     .with_recursion();
+    /// ```
+    pub fn with_recursion(mut self) -> Self {
+        self.recursive = true;
+
+        self
+    }
 }
 
 impl AsJson for LookupRequestBody {
     fn to_json(&self) -> Result<Json, AsJsonError> {
-        Ok(json!({
+        let mut body = json!({
             "public_key": self.public_key.to_base64(),
             "type": self.client_type.map(|value| value.to_string())
-        }))
+        });
+
+        if self.recursive {
+            body["recursive"] = json!(true);
+        }
+
+        Ok(body)
     }
 
     fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
@@ -63,7 +102,16 @@ impl AsJson for LookupRequestBody {
                 .and_then(Json::as_str)
                 .map(ClientType::from_str)
                 .transpose()
-                .map_err(|_| AsJsonError::FieldValueInvalid("Invalid client type value"))?
+                .map_err(|_| AsJsonError::FieldValueInvalid("Invalid client type value"))?,
+
+            recursive: match json.get("recursive") {
+                Some(recursive) if !recursive.is_null() => {
+                    recursive.as_bool()
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("recursive"))?
+                }
+
+                _ => false
+            }
         })
     }
 }
@@ -84,6 +132,10 @@ mod tests {
 
         assert_eq!(LookupRequestBody::from_json(&request.to_json()?)?, request);
 
+        let request = LookupRequestBody::new(public.clone(), None).with_recursion();
+
+        assert_eq!(LookupRequestBody::from_json(&request.to_json()?)?, request);
+
         Ok(())
     }
 }
@@ -100,7 +100,7 @@ impl LookupResponseBody {
     /// let client_info = ClientInfo::thin();
     /// 
     /// let client = Client::new(client_public, certificate, client_info);
-    /// let server = Server::new(server_public, "example.org");
+    /// let server = Server::new(server_public, "example.org".parse().unwrap());
     /// 
     /// let response_body = LookupResponseBody::remote(client, server, true);
     /// ```
@@ -125,9 +125,9 @@ impl LookupResponseBody {
     /// use hyperborealib::rest_api::prelude::*;
     /// 
     /// let response_body = LookupResponseBody::hint(vec![
-    ///     Server::new(SecretKey::random().public_key(), "example1.org"),
-    ///     Server::new(SecretKey::random().public_key(), "example2.org"),
-    ///     Server::new(SecretKey::random().public_key(), "example3.org")
+    ///     Server::new(SecretKey::random().public_key(), "example1.org".parse().unwrap()),
+    ///     Server::new(SecretKey::random().public_key(), "example2.org".parse().unwrap()),
+    ///     Server::new(SecretKey::random().public_key(), "example3.org".parse().unwrap())
     /// ]);
     /// ```
     pub fn hint(servers: impl Into<Vec<Server>>) -> Self {
@@ -27,16 +27,24 @@ mod clients;
 mod servers;
 mod info;
 mod connect;
+mod disconnect;
 mod announce;
 mod lookup;
 mod send;
+mod send_batch;
 mod poll;
+mod ack;
+mod subscribe;
 
 pub use clients::*;
 pub use servers::*;
 pub use info::*;
 pub use connect::*;
+pub use disconnect::*;
 pub use announce::*;
 pub use lookup::*;
 pub use send::*;
+pub use send_batch::*;
 pub use poll::*;
+pub use ack::*;
+pub use subscribe::*;
@@ -31,6 +31,11 @@ impl PollRequest {
         Self(Request::new(client_secret, PollRequestBody::new(channel, limit)))
     }
 
+    #[inline]
+    pub fn leased(client_secret: &SecretKey, channel: impl ToString, limit: Option<u64>, lease: u64) -> Self {
+        Self(Request::new(client_secret, PollRequestBody::leased(channel, limit, lease)))
+    }
+
     #[inline]
     /// Validate the request.
     /// 
@@ -9,7 +9,21 @@ use crate::rest_api::prelude::*;
 /// Refer to `PollRequest` for details.
 pub struct PollRequestBody {
     pub channel: String,
-    pub limit: Option<u64>
+    pub limit: Option<u64>,
+
+    /// Visibility timeout in seconds of the polled messages.
+    /// 
+    /// If set, then polled messages are not removed
+    /// from the inbox but hidden for this time, and must be
+    /// acknowledged by the `POST /api/v1/ack` request.
+    pub lease: Option<u64>,
+
+    /// Long polling timeout in seconds.
+    /// 
+    /// If set, then the server holds the request until
+    /// a message arrives to the channel or the timeout
+    /// expires. Servers can limit this timeout or ignore it.
+    pub wait: Option<u64>
 }
 
 impl PollRequestBody {
@@ -33,17 +47,83 @@ impl PollRequestBody {
     pub fn new(channel: impl ToString, limit: Option<u64>) -> Self {
         Self {
             channel: channel.to_string(),
-            limit
+            limit,
+            lease: None,
+            wait: None
         }
     }
+
+    #[inline]
+    /// Create new `POST /api/v1/poll` request body
+    /// with leased messages.
+    /// 
+    /// - `channel` must be a name of the channel from which
+    ///   we want to poll the messages.
+    /// 
+    /// - `limit` must be a maximal number of messages
+    ///   we want to poll, or `None` if all.
+    /// 
+    /// - `lease` must be a visibility timeout in seconds.
+    ///   Polled messages will be returned to the inbox
+    ///   if they're not acknowledged during this time.
+    ///   Servers can reduce too long timeouts.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use hyperborealib::rest_api::prelude::*;
+    /// 
+    /// // Lease one message from "example channel" channel for 30 seconds
+    /// let request_body = PollRequestBody::leased("example channel", Some(1), 30);
+    /// ```
+    pub fn leased(channel: impl ToString, limit: Option<u64>, lease: u64) -> Self {
+        Self {
+            channel: channel.to_string(),
+            limit,
+            lease: Some(lease),
+            wait: None
+        }
+    }
+
+    #[inline]
+    /// Set long polling timeout in seconds.
+    /// 
+    /// Server will answer the request as soon as a message
+    /// arrives to the channel, or with an empty list
+    /// of messages when the timeout expires.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use hyperborealib::rest_api::prelude::*;
+    /// 
+    /// // Wait up to 30 seconds for messages in "example channel" channel
+    /// let request_body = PollRequestBody::new("example channel", None)
+    ///     .with_wait(30);
+    /// ```
+    pub fn with_wait(mut self, wait: u64) -> Self {
+        self.wait = Some(wait);
+
+        self
+    }
 }
 
 impl AsJson for PollRequestBody {
     fn to_json(&self) -> Result<Json, AsJsonError> {
-        Ok(json!({
+        let mut body = json!({
             "channel": self.channel,
             "limit": self.limit
-        }))
+        });
+
+        if let Some(lease) = self.lease {
+            body["lease"] = json!(lease);
+        }
+
+        if let Some(wait) = self.wait {
+            body["wait"] = json!(wait);
+        }
+
+        Ok(body)
     }
 
     fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
@@ -63,7 +143,29 @@ impl AsJson for PollRequestBody {
                             .map(Some)
                             .ok_or_else(|| AsJsonError::FieldValueInvalid("channel"))
                     }
-                })?
+                })?,
+
+            lease: match json.get("lease") {
+                Some(lease) if !lease.is_null() => {
+                    let lease = lease.as_u64()
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("lease"))?;
+
+                    Some(lease)
+                }
+
+                _ => None
+            },
+
+            wait: match json.get("wait") {
+                Some(wait) if !wait.is_null() => {
+                    let wait = wait.as_u64()
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("wait"))?;
+
+                    Some(wait)
+                }
+
+                _ => None
+            }
         })
     }
 }
@@ -84,6 +186,14 @@ mod tests {
 
         assert_eq!(PollRequestBody::from_json(&request.to_json()?)?, request);
 
+        let request = PollRequestBody::leased("Hello, World!", Some(5), 30);
+
+        assert_eq!(PollRequestBody::from_json(&request.to_json()?)?, request);
+
+        let request = PollRequestBody::leased("Hello, World!", None, 30).with_wait(60);
+
+        assert_eq!(PollRequestBody::from_json(&request.to_json()?)?, request);
+
         Ok(())
     }
 }
@@ -9,7 +9,13 @@ use crate::rest_api::prelude::*;
 /// Refer to `PollResponse` for details.
 pub struct PollResponseBody {
     pub messages: Vec<MessageInfo>,
-    pub remaining: u64
+    pub remaining: u64,
+
+    /// Delivery IDs of the leased messages.
+    /// 
+    /// Set only if the messages were leased.
+    /// Every ID belongs to the message with the same index.
+    pub delivery_ids: Option<Vec<u64>>
 }
 
 impl PollResponseBody {
@@ -23,20 +29,44 @@ impl PollResponseBody {
     pub fn new(messages: impl Into<Vec<MessageInfo>>, remaining: u64) -> Self {
         Self {
             messages: messages.into(),
-            remaining
+            remaining,
+            delivery_ids: None
+        }
+    }
+
+    /// Create new `POST /api/v1/poll` response body
+    /// with leased messages.
+    /// 
+    /// - `messages` must be a vector of delivery IDs and
+    ///   messages info leased from the server inbox.
+    /// 
+    /// - `remaining` must be a number of remaining inbox messages.
+    pub fn leased(messages: impl IntoIterator<Item = (u64, MessageInfo)>, remaining: u64) -> Self {
+        let (delivery_ids, messages) = messages.into_iter().unzip();
+
+        Self {
+            messages,
+            remaining,
+            delivery_ids: Some(delivery_ids)
         }
     }
 }
 
 impl AsJson for PollResponseBody {
     fn to_json(&self) -> Result<Json, AsJsonError> {
-        Ok(json!({
+        let mut body = json!({
             "messages": self.messages.iter()
                 .map(MessageInfo::to_json)
                 .collect::<Result<Vec<_>, _>>()?,
 
             "remaining": self.remaining
-        }))
+        });
+
+        if let Some(delivery_ids) = &self.delivery_ids {
+            body["delivery_ids"] = json!(delivery_ids);
+        }
+
+        Ok(body)
     }
 
     fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
@@ -52,7 +82,19 @@ impl AsJson for PollResponseBody {
 
             remaining: json.get("remaining")
                 .and_then(Json::as_u64)
-                .ok_or_else(|| AsJsonError::FieldNotFound("remaining"))?
+                .ok_or_else(|| AsJsonError::FieldNotFound("remaining"))?,
+
+            delivery_ids: match json.get("delivery_ids") {
+                Some(delivery_ids) if !delivery_ids.is_null() => {
+                    let delivery_ids = delivery_ids.as_array()
+                        .and_then(|ids| ids.iter().map(Json::as_u64).collect::<Option<Vec<_>>>())
+                        .ok_or_else(|| AsJsonError::FieldValueInvalid("delivery_ids"))?;
+
+                    Some(delivery_ids)
+                }
+
+                _ => None
+            }
         })
     }
 }
@@ -74,7 +116,7 @@ mod tests {
         let info = ClientInfo::thin();
 
         let client = Client::new(client.public_key(), certificate, info);
-        let server = Server::new(server.public_key(), "example.org");
+        let server = Server::new(server.public_key(), "example.org".parse().unwrap());
 
         let sender = Sender::new(client, server.clone());
 
@@ -83,7 +125,11 @@ mod tests {
 
         let info = MessageInfo::now(sender, "Hello, World!", message);
 
-        let response = PollResponseBody::new(vec![info], 100);
+        let response = PollResponseBody::new(vec![info.clone()], 100);
+
+        assert_eq!(PollResponseBody::from_json(&response.to_json()?)?, response);
+
+        let response = PollResponseBody::leased(vec![(1, info)], 100);
 
         assert_eq!(PollResponseBody::from_json(&response.to_json()?)?, response);
 
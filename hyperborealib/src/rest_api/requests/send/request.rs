@@ -85,7 +85,7 @@ mod tests {
         let cert = ConnectionCertificate::new(&client, server.public_key());
 
         let client = Client::new(client.public_key(), cert, info);
-        let server = Server::new(server.public_key(), "amogus");
+        let server = Server::new(server.public_key(), "amogus".parse().unwrap());
 
         let sender = Sender::new(client, server.clone());
 
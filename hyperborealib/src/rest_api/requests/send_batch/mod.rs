@@ -0,0 +1,93 @@
+use serde_json::Value as Json;
+
+use crate::crypto::prelude::*;
+use crate::rest_api::prelude::*;
+
+mod request;
+mod response;
+
+pub use request::*;
+pub use response::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `POST /api/v1/send_batch` request.
+/// 
+/// This request is used to store many messages in a server's
+/// inbox using a single signed request. Unlike `SendRequest`,
+/// every message is processed independently and gets its own
+/// result in the response.
+/// 
+/// All the receivers must be connected to this server.
+/// Batched messages are not forwarded to other servers.
+pub struct SendBatchRequest(pub Request<SendBatchRequestBody>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `POST /api/v1/send_batch` response.
+pub struct SendBatchResponse(pub Response<SendBatchResponseBody>);
+
+impl SendBatchRequest {
+    #[inline]
+    pub fn new(client_secret: &SecretKey, sender: Sender, messages: impl Into<Vec<SendBatchMessage>>) -> Self {
+        Self(Request::new(client_secret, SendBatchRequestBody::new(sender, messages)))
+    }
+
+    #[inline]
+    /// Validate the request.
+    /// 
+    /// Calls `validate()` function on the request's body.
+    pub fn validate(&self) -> Result<bool, ValidationError> {
+        self.0.validate()
+    }
+}
+
+impl AsJson for SendBatchRequest {
+    #[inline]
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        self.0.to_json()
+    }
+
+    #[inline]
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        Ok(Self(Request::from_json(json)?))
+    }
+}
+
+impl SendBatchResponse {
+    pub fn success(status: ResponseStatus, server_secret: &SecretKey, proof_seed: u64, response_body: SendBatchResponseBody) -> Self {
+        let proof = server_secret.create_signature(proof_seed.to_be_bytes());
+
+        Self(Response::success(
+            status,
+            server_secret.public_key(),
+            proof,
+            response_body
+        ))
+    }
+
+    #[inline]
+    pub fn error(status: ResponseStatus, reason: impl ToString) -> Self {
+        Self(Response::error(status, reason))
+    }
+
+    #[inline]
+    /// Validate the response.
+    /// 
+    /// Calls `validate()` function on the response's body.
+    pub fn validate(&self, proof_seed: u64) -> Result<bool, ValidationError> {
+        self.0.validate(proof_seed)
+    }
+}
+
+impl AsJson for SendBatchResponse {
+    #[inline]
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        self.0.to_json()
+    }
+
+    #[inline]
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        Ok(Self(Response::from_json(json)?))
+    }
+}
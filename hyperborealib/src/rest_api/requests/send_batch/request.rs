@@ -0,0 +1,142 @@
+use serde_json::{json, Value as Json};
+
+use crate::crypto::asymmetric::PublicKey;
+
+use crate::rest_api::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Single message of the `POST /api/v1/send_batch` request.
+pub struct SendBatchMessage {
+    pub receiver_public: PublicKey,
+    pub channel: String,
+    pub message: Message
+}
+
+impl SendBatchMessage {
+    #[inline]
+    pub fn new(receiver_public: PublicKey, channel: impl ToString, message: Message) -> Self {
+        Self {
+            receiver_public,
+            channel: channel.to_string(),
+            message
+        }
+    }
+}
+
+impl AsJson for SendBatchMessage {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "receiver": {
+                "public_key": self.receiver_public.to_base64()
+            },
+            "channel": self.channel,
+            "message": self.message.to_json()?
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(receiver) = json.get("receiver") else {
+            return Err(AsJsonError::FieldNotFound("receiver"));
+        };
+
+        Ok(Self {
+            receiver_public: receiver.get("public_key")
+                .and_then(Json::as_str)
+                .ok_or_else(|| AsJsonError::FieldNotFound("receiver.public_key"))
+                .map(PublicKey::from_base64)??,
+
+            channel: json.get("channel")
+                .and_then(Json::as_str)
+                .map(String::from)
+                .ok_or_else(|| AsJsonError::FieldNotFound("channel"))?,
+
+            message: json.get("message")
+                .map(Message::from_json)
+                .ok_or_else(|| AsJsonError::FieldNotFound("message"))??
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `POST /api/v1/send_batch` request body.
+/// 
+/// Refer to `SendBatchRequest` for details.
+pub struct SendBatchRequestBody {
+    pub sender: Sender,
+    pub messages: Vec<SendBatchMessage>
+}
+
+impl SendBatchRequestBody {
+    #[inline]
+    pub fn new(sender: Sender, messages: impl Into<Vec<SendBatchMessage>>) -> Self {
+        Self {
+            sender,
+            messages: messages.into()
+        }
+    }
+}
+
+impl AsJson for SendBatchRequestBody {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "sender": self.sender.to_json()?,
+            "messages": self.messages.iter()
+                .map(SendBatchMessage::to_json)
+                .collect::<Result<Vec<_>, _>>()?
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        Ok(Self {
+            sender: json.get("sender")
+                .map(Sender::from_json)
+                .ok_or_else(|| AsJsonError::FieldNotFound("sender"))??,
+
+            messages: json.get("messages")
+                .and_then(Json::as_array)
+                .map(|messages| {
+                    messages.iter()
+                        .map(SendBatchMessage::from_json)
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .ok_or_else(|| AsJsonError::FieldNotFound("messages"))??
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::crypto::asymmetric::SecretKey;
+
+    use super::*;
+
+    #[test]
+    fn serialize() -> Result<(), AsJsonError> {
+        let client = SecretKey::random();
+        let server = SecretKey::random();
+
+        let info = ClientInfo::thin();
+        let cert = ConnectionCertificate::new(&client, server.public_key());
+
+        let client = Client::new(client.public_key(), cert, info);
+        let server = Server::new(server.public_key(), "amogus".parse().unwrap());
+
+        let sender = Sender::new(client, server.clone());
+
+        let message_encoding = MessageEncoding::from_str("base64").unwrap();
+        let message = Message::new("content", "sign", message_encoding);
+
+        let request = SendBatchRequestBody::new(sender, vec![
+            SendBatchMessage::new(server.public_key.clone(), "amogus", message.clone()),
+            SendBatchMessage::new(SecretKey::random().public_key(), "sus", message)
+        ]);
+
+        assert_eq!(SendBatchRequestBody::from_json(&request.to_json()?)?, request);
+
+        Ok(())
+    }
+}
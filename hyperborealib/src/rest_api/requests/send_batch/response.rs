@@ -0,0 +1,126 @@
+use serde_json::{json, Value as Json};
+
+use crate::rest_api::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Result of a single message of the `POST /api/v1/send_batch` request.
+/// 
+/// Status codes are the same as of the `POST /api/v1/send` request.
+pub struct SendBatchResult {
+    pub status: ResponseStatus,
+
+    /// Reason of the failure.
+    /// 
+    /// Set only if the message wasn't stored.
+    pub reason: Option<String>
+}
+
+impl SendBatchResult {
+    #[inline]
+    pub fn success() -> Self {
+        Self {
+            status: ResponseStatus::Success,
+            reason: None
+        }
+    }
+
+    #[inline]
+    pub fn error(status: ResponseStatus, reason: impl ToString) -> Self {
+        Self {
+            status,
+            reason: Some(reason.to_string())
+        }
+    }
+}
+
+impl AsJson for SendBatchResult {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        let mut result = json!({
+            "status": self.status.to_code()
+        });
+
+        if let Some(reason) = &self.reason {
+            result["reason"] = json!(reason);
+        }
+
+        Ok(result)
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(status) = json.get("status") else {
+            return Err(AsJsonError::FieldNotFound("status"));
+        };
+
+        let Some(status) = status.as_u64().and_then(ResponseStatus::from_code) else {
+            return Err(AsJsonError::FieldValueInvalid("status"));
+        };
+
+        Ok(Self {
+            status,
+            reason: json.get("reason")
+                .and_then(Json::as_str)
+                .map(String::from)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `POST /api/v1/send_batch` response body.
+/// 
+/// Refer to `SendBatchResponse` for details.
+pub struct SendBatchResponseBody {
+    /// Results of the messages in the same
+    /// order as they were sent in the request.
+    pub results: Vec<SendBatchResult>
+}
+
+impl SendBatchResponseBody {
+    #[inline]
+    pub fn new(results: impl Into<Vec<SendBatchResult>>) -> Self {
+        Self {
+            results: results.into()
+        }
+    }
+}
+
+impl AsJson for SendBatchResponseBody {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "results": self.results.iter()
+                .map(SendBatchResult::to_json)
+                .collect::<Result<Vec<_>, _>>()?
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        Ok(Self {
+            results: json.get("results")
+                .and_then(Json::as_array)
+                .map(|results| {
+                    results.iter()
+                        .map(SendBatchResult::from_json)
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .ok_or_else(|| AsJsonError::FieldNotFound("results"))??
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() -> Result<(), AsJsonError> {
+        let response = SendBatchResponseBody::new(vec![
+            SendBatchResult::success(),
+            SendBatchResult::error(ResponseStatus::ClientNotConnected, "Receiver is not connected to this server")
+        ]);
+
+        assert_eq!(SendBatchResponseBody::from_json(&response.to_json()?)?, response);
+
+        Ok(())
+    }
+}
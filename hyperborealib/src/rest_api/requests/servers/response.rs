@@ -34,9 +34,9 @@ impl ServersResponse {
     /// use hyperborealib::rest_api::prelude::*;
     /// 
     /// let response = ServersResponse::new(vec![
-    ///     Server::new(SecretKey::random().public_key(), "example1.org"),
-    ///     Server::new(SecretKey::random().public_key(), "example2.org"),
-    ///     Server::new(SecretKey::random().public_key(), "example3.org")
+    ///     Server::new(SecretKey::random().public_key(), "example1.org".parse().unwrap()),
+    ///     Server::new(SecretKey::random().public_key(), "example2.org".parse().unwrap()),
+    ///     Server::new(SecretKey::random().public_key(), "example3.org".parse().unwrap())
     /// ]);
     /// ```
     pub fn new(servers: impl Into<Vec<Server>>) -> Self {
@@ -50,7 +50,7 @@ impl ServersResponse {
 impl AsJson for ServersResponse {
     fn to_json(&self) -> Result<Json, AsJsonError> {
         match self.standard {
-            1 => Ok(json!({
+            1 | 2 => Ok(json!({
                 "standard": self.standard,
                 "servers": self.servers.iter()
                     .map(AsJson::to_json)
@@ -67,7 +67,7 @@ impl AsJson for ServersResponse {
         };
 
         match standard {
-            1 => {
+            1 | 2 => {
                 let Some(servers) = json.get("servers").and_then(Json::as_array) else {
                     return Err(AsJsonError::FieldNotFound("servers"));
                 };
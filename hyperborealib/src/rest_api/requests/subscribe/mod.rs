@@ -0,0 +1,95 @@
+use serde_json::Value as Json;
+
+use crate::crypto::prelude::*;
+use crate::rest_api::prelude::*;
+
+mod request;
+mod response;
+
+pub use request::*;
+pub use response::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `GET /api/v1/subscribe` WebSocket request.
+/// 
+/// This request is sent by the client over the WebSocket
+/// connection as an answer to the `SubscribeChallenge`
+/// message sent by the server. Once accepted, the server
+/// pushes new messages from the client's inbox
+/// (see `SubscribeUpdate`) for the chosen channels
+/// as soon as they arrive.
+/// 
+/// Challenge is included in the signed request body,
+/// so the request can't be reused in another connection.
+pub struct SubscribeRequest(pub Request<SubscribeRequestBody>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `GET /api/v1/subscribe` WebSocket response.
+pub struct SubscribeResponse(pub Response<SubscribeResponseBody>);
+
+impl SubscribeRequest {
+    #[inline]
+    pub fn new(client_secret: &SecretKey, challenge: u64, channels: impl IntoIterator<Item = impl ToString>) -> Self {
+        Self(Request::new(client_secret, SubscribeRequestBody::new(challenge, channels)))
+    }
+
+    #[inline]
+    /// Validate the request.
+    /// 
+    /// Calls `validate()` function on the request's body.
+    pub fn validate(&self) -> Result<bool, ValidationError> {
+        self.0.validate()
+    }
+}
+
+impl AsJson for SubscribeRequest {
+    #[inline]
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        self.0.to_json()
+    }
+
+    #[inline]
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        Ok(Self(Request::from_json(json)?))
+    }
+}
+
+impl SubscribeResponse {
+    pub fn success(status: ResponseStatus, server_secret: &SecretKey, proof_seed: u64, response_body: SubscribeResponseBody) -> Self {
+        let proof = server_secret.create_signature(proof_seed.to_be_bytes());
+
+        Self(Response::success(
+            status,
+            server_secret.public_key(),
+            proof,
+            response_body
+        ))
+    }
+
+    #[inline]
+    pub fn error(status: ResponseStatus, reason: impl ToString) -> Self {
+        Self(Response::error(status, reason))
+    }
+
+    #[inline]
+    /// Validate the response.
+    /// 
+    /// Calls `validate()` function on the response's body.
+    pub fn validate(&self, proof_seed: u64) -> Result<bool, ValidationError> {
+        self.0.validate(proof_seed)
+    }
+}
+
+impl AsJson for SubscribeResponse {
+    #[inline]
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        self.0.to_json()
+    }
+
+    #[inline]
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        Ok(Self(Response::from_json(json)?))
+    }
+}
@@ -0,0 +1,138 @@
+use serde_json::{json, Value as Json};
+
+use crate::crypto::prelude::*;
+use crate::rest_api::prelude::*;
+
+use crate::STANDARD_VERSION;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `GET /api/v1/subscribe` challenge message.
+/// 
+/// This message is sent by the server right after
+/// the WebSocket connection is opened. Client must
+/// include the challenge to the `SubscribeRequestBody`.
+pub struct SubscribeChallenge {
+    pub standard: u64,
+    pub challenge: u64
+}
+
+impl SubscribeChallenge {
+    #[inline]
+    /// Create new subscription challenge with random value.
+    pub fn random() -> Self {
+        Self {
+            standard: STANDARD_VERSION,
+            challenge: safe_random_u64_long()
+        }
+    }
+}
+
+impl AsJson for SubscribeChallenge {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "standard": self.standard,
+            "challenge": self.challenge
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(standard) = json.get("standard").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("standard"));
+        };
+
+        let Some(challenge) = json.get("challenge").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("challenge"));
+        };
+
+        Ok(Self {
+            standard,
+            challenge
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `GET /api/v1/subscribe` request body.
+/// 
+/// Refer to `SubscribeRequest` for details.
+pub struct SubscribeRequestBody {
+    pub challenge: u64,
+    pub channels: Vec<String>
+}
+
+impl SubscribeRequestBody {
+    #[inline]
+    /// Create new `GET /api/v1/subscribe` request body.
+    /// 
+    /// - `challenge` must be a value received
+    ///   in the `SubscribeChallenge` message.
+    /// 
+    /// - `channels` must be a list of channels
+    ///   from which we want to receive messages.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use hyperborealib::rest_api::prelude::*;
+    /// 
+    /// let challenge = SubscribeChallenge::random();
+    /// 
+    /// let request_body = SubscribeRequestBody::new(challenge.challenge, ["example channel"]);
+    /// ```
+    pub fn new(challenge: u64, channels: impl IntoIterator<Item = impl ToString>) -> Self {
+        Self {
+            challenge,
+            channels: channels.into_iter()
+                .map(|channel| channel.to_string())
+                .collect()
+        }
+    }
+}
+
+impl AsJson for SubscribeRequestBody {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "challenge": self.challenge,
+            "channels": self.channels
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(challenge) = json.get("challenge").and_then(Json::as_u64) else {
+            return Err(AsJsonError::FieldNotFound("challenge"));
+        };
+
+        let Some(channels) = json.get("channels").and_then(Json::as_array) else {
+            return Err(AsJsonError::FieldNotFound("channels"));
+        };
+
+        let Some(channels) = channels.iter().map(|channel| channel.as_str().map(String::from)).collect() else {
+            return Err(AsJsonError::FieldValueInvalid("channels"));
+        };
+
+        Ok(Self {
+            challenge,
+            channels
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() -> Result<(), AsJsonError> {
+        let challenge = SubscribeChallenge::random();
+
+        assert_eq!(SubscribeChallenge::from_json(&challenge.to_json()?)?, challenge);
+
+        let request = SubscribeRequestBody::new(challenge.challenge, ["Hello", "World"]);
+
+        assert_eq!(SubscribeRequestBody::from_json(&request.to_json()?)?, request);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use serde_json::{json, Value as Json};
+
+use crate::rest_api::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `GET /api/v1/subscribe` response body.
+/// 
+/// Refer to `SubscribeResponse` for details.
+pub struct SubscribeResponseBody {
+    pub channels: Vec<String>
+}
+
+impl SubscribeResponseBody {
+    #[inline]
+    /// Create new `GET /api/v1/subscribe` response body.
+    /// 
+    /// - `channels` must be a list of channels
+    ///   the client was subscribed to.
+    pub fn new(channels: impl Into<Vec<String>>) -> Self {
+        Self {
+            channels: channels.into()
+        }
+    }
+}
+
+impl AsJson for SubscribeResponseBody {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "channels": self.channels
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(channels) = json.get("channels").and_then(Json::as_array) else {
+            return Err(AsJsonError::FieldNotFound("channels"));
+        };
+
+        let Some(channels) = channels.iter().map(|channel| channel.as_str().map(String::from)).collect() else {
+            return Err(AsJsonError::FieldValueInvalid("channels"));
+        };
+
+        Ok(Self {
+            channels
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// `GET /api/v1/subscribe` update message.
+/// 
+/// This message is pushed by the server every time
+/// new messages arrive to the subscribed channels.
+pub struct SubscribeUpdate {
+    pub messages: Vec<MessageInfo>
+}
+
+impl SubscribeUpdate {
+    #[inline]
+    pub fn new(messages: impl Into<Vec<MessageInfo>>) -> Self {
+        Self {
+            messages: messages.into()
+        }
+    }
+}
+
+impl AsJson for SubscribeUpdate {
+    fn to_json(&self) -> Result<Json, AsJsonError> {
+        Ok(json!({
+            "messages": self.messages.iter()
+                .map(MessageInfo::to_json)
+                .collect::<Result<Vec<_>, _>>()?
+        }))
+    }
+
+    fn from_json(json: &Json) -> Result<Self, AsJsonError> where Self: Sized {
+        let Some(messages) = json.get("messages").and_then(Json::as_array) else {
+            return Err(AsJsonError::FieldNotFound("messages"));
+        };
+
+        Ok(Self {
+            messages: messages.iter()
+                .map(MessageInfo::from_json)
+                .collect::<Result<Vec<_>, _>>()?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::crypto::asymmetric::SecretKey;
+
+    use super::*;
+
+    #[test]
+    fn serialize() -> Result<(), AsJsonError> {
+        let response = SubscribeResponseBody::new(vec![String::from("Hello, World!")]);
+
+        assert_eq!(SubscribeResponseBody::from_json(&response.to_json()?)?, response);
+
+        let client = SecretKey::random();
+        let server = SecretKey::random();
+
+        let certificate = ConnectionCertificate::new(&client, server.public_key());
+        let info = ClientInfo::thin();
+
+        let client = Client::new(client.public_key(), certificate, info);
+        let server = Server::new(server.public_key(), "example.org".parse().unwrap());
+
+        let sender = Sender::new(client, server.clone());
+
+        let encoding = MessageEncoding::from_str("base64").unwrap();
+        let message = Message::new("content", "sign", encoding);
+
+        let update = SubscribeUpdate::new(vec![MessageInfo::now(sender, "Hello, World!", message)]);
+
+        assert_eq!(SubscribeUpdate::from_json(&update.to_json()?)?, update);
+
+        Ok(())
+    }
+}
@@ -116,6 +116,20 @@ impl<T> Response<T> {
         }
     }
 
+    /// Change `standard` field of the response header.
+    /// 
+    /// Servers answer using the same standard version
+    /// as the one used by the request, so clients
+    /// implementing older standards could read them.
+    pub fn with_standard(mut self, new_standard: u64) -> Self {
+        match &mut self {
+            Self::Success { standard, .. } |
+            Self::Error { standard, .. } => *standard = new_standard
+        }
+
+        self
+    }
+
     /// Get `status` field from the response header.
     /// 
     /// This is a helper function for easier work
@@ -192,7 +206,7 @@ impl<T: AsJson> AsJson for Response<T> {
         let value = match self {
             Self::Success { standard, status, public_key, proof_sign, response } => {
                 match standard {
-                    1 => json!({
+                    1 | 2 => json!({
                         "standard": standard,
                         "status": status.to_code(),
                         "public_key": public_key.to_base64(),
@@ -208,7 +222,7 @@ impl<T: AsJson> AsJson for Response<T> {
 
             Self::Error { standard, status, reason } => {
                 match standard {
-                    1 => json!({
+                    1 | 2 => json!({
                         "standard": standard,
                         "status": status.to_code(),
                         "reason": reason
@@ -228,7 +242,7 @@ impl<T: AsJson> AsJson for Response<T> {
         };
 
         match standard {
-            1 => {
+            1 | 2 => {
                 let Some(status) = json.get("status") else {
                     return Err(AsJsonError::FieldNotFound("status"));
                 };
@@ -13,6 +13,9 @@ pub enum ResponseStatus {
     /// Protocol error - 301
     RequestValidationFailed,
 
+    /// Protocol error - 302
+    RequestReplayed,
+
     /// Protocol error - 310
     ClientLookupTimeout,
 
@@ -41,6 +44,7 @@ impl ResponseStatus {
             // Protocol error
             300 => Self::InvalidRequestStructure,
             301 => Self::RequestValidationFailed,
+            302 => Self::RequestReplayed,
 
             // Protocol error - lookup error
             310 => Self::ClientLookupTimeout,
@@ -68,6 +72,7 @@ impl ResponseStatus {
             // Protocol error
             Self::InvalidRequestStructure => 300,
             Self::RequestValidationFailed => 301,
+            Self::RequestReplayed         => 302,
 
             // Protocol error - lookup error
             Self::ClientLookupTimeout => 310,
@@ -66,17 +66,20 @@ impl AsJson for Client {
 
 #[cfg(test)]
 pub(crate) mod tests {
-    use super::connection_certificate::tests::get_certificate;
-
     use super::*;
 
     pub fn get_client() -> Client {
-        let public = SecretKey::random().public_key();
+        get_client_for(SecretKey::random().public_key())
+    }
+
+    /// Get client connected to the server with given public key.
+    pub fn get_client_for(server_public: PublicKey) -> Client {
+        let secret = SecretKey::random();
 
-        let certificate = get_certificate();
+        let certificate = ConnectionCertificate::new(&secret, server_public);
         let info = ClientInfo::thin();
 
-        Client::new(public, certificate, info)
+        Client::new(secret.public_key(), certificate, info)
     }
 
     #[test]
@@ -100,7 +100,7 @@ impl Message {
     /// let sender = SecretKey::random();
     /// let receiver = SecretKey::random().public_key();
     /// 
-    /// let encoding = MessageEncoding::from_str("base64/aes256-gcm/deflate").unwrap();
+    /// let encoding = MessageEncoding::from_str("base64/aes256-gcm-rn/deflate").unwrap();
     /// let level = CompressionLevel::Quality;
     /// 
     /// let message = Message::create(
@@ -147,7 +147,7 @@ impl Message {
     /// let sender = SecretKey::random();
     /// let receiver = SecretKey::random();
     /// 
-    /// let encoding = MessageEncoding::from_str("base64/aes256-gcm/deflate").unwrap();
+    /// let encoding = MessageEncoding::from_str("base64/aes256-gcm-rn/deflate").unwrap();
     /// 
     /// // Create the message (compress, encrypt and sign)
     /// let message = Message::create(
@@ -193,11 +193,17 @@ pub(crate) mod tests {
 
             MessageEncoding::from_str("base64/aes256-gcm")?,
             MessageEncoding::from_str("base64/chacha20-poly1305")?,
+            MessageEncoding::from_str("base64/aes256-gcm-rn")?,
+            MessageEncoding::from_str("base64/xchacha20-poly1305")?,
 
             MessageEncoding::from_str("base64/aes256-gcm/deflate")?,
             MessageEncoding::from_str("base64/chacha20-poly1305/deflate")?,
+            MessageEncoding::from_str("base64/aes256-gcm-rn/deflate")?,
+            MessageEncoding::from_str("base64/xchacha20-poly1305/deflate")?,
             MessageEncoding::from_str("base64/aes256-gcm/brotli")?,
-            MessageEncoding::from_str("base64/chacha20-poly1305/brotli")?
+            MessageEncoding::from_str("base64/chacha20-poly1305/brotli")?,
+            MessageEncoding::from_str("base64/aes256-gcm-rn/brotli")?,
+            MessageEncoding::from_str("base64/xchacha20-poly1305/brotli")?
         ])
     }
 
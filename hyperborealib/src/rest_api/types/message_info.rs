@@ -59,7 +59,7 @@ impl MessageInfo {
     /// 
     /// let server = Server::new(
     ///     sender_server_secret.public_key(),
-    ///     "example.org"
+    ///     "example.org".parse().unwrap()
     /// );
     /// 
     /// let sender = Sender::new(client, server);
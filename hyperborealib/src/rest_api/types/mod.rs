@@ -17,6 +17,7 @@ pub(crate) mod connection_token;
 pub(crate) mod connection_certificate;
 pub(crate) mod client;
 pub(crate) mod server;
+pub(crate) mod server_address;
 pub(crate) mod message_info;
 pub(crate) mod message_encoding;
 pub(crate) mod sender;
@@ -28,6 +29,7 @@ pub use connection_token::*;
 pub use connection_certificate::*;
 pub use client::*;
 pub use server::*;
+pub use server_address::*;
 pub use message_info::*;
 pub use message_encoding::*;
 pub use sender::*;
@@ -40,7 +40,7 @@ impl Sender {
     /// let info = ClientInfo::thin();
     /// 
     /// let client = Client::new(client.public_key(), certificate, info);
-    /// let server = Server::new(server.public_key(), "example.org");
+    /// let server = Server::new(server.public_key(), "example.org".parse().unwrap());
     /// 
     /// let sender = Sender::new(client, server);
     /// ```
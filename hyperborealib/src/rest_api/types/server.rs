@@ -11,7 +11,7 @@ use crate::rest_api::prelude::*;
 /// hyperborea protocol's paper.
 pub struct Server {
     pub public_key: PublicKey,
-    pub address: String
+    pub address: ServerAddress
 }
 
 impl Server {
@@ -23,9 +23,8 @@ impl Server {
     ///   creation of the shared secret encryption keys.
     /// 
     /// - `address` must contain globally available address of
-    ///   this server (either IP or domain name). It may contain
-    ///   any arbitrary data, but then this server will not be seen
-    ///   by other network participants.
+    ///   this server (either IP or domain name). Only `http` and
+    ///   `https` addresses can be shared with other servers.
     /// 
     /// # Example
     /// 
@@ -35,12 +34,12 @@ impl Server {
     /// 
     /// let server_public = SecretKey::random().public_key();
     /// 
-    /// let server = Server::new(server_public, "example.org");
+    /// let server = Server::new(server_public, "https://example.org".parse().unwrap());
     /// ```
-    pub fn new(public_key: PublicKey, address: impl ToString) -> Self {
+    pub fn new(public_key: PublicKey, address: ServerAddress) -> Self {
         Self {
             public_key,
-            address: address.to_string()
+            address
         }
     }
 }
@@ -49,7 +48,7 @@ impl AsJson for Server {
     fn to_json(&self) -> Result<Json, AsJsonError> {
         Ok(json!({
             "public_key": self.public_key.to_base64(),
-            "address": self.address
+            "address": self.address.to_string()
         }))
     }
 
@@ -62,9 +61,18 @@ impl AsJson for Server {
             return Err(AsJsonError::FieldNotFound("address"));
         };
 
+        let address = address.parse::<ServerAddress>()
+            .map_err(|_| AsJsonError::FieldValueInvalid("address"))?;
+
+        // Servers are shared between the network members,
+        // so their addresses must be globally available
+        if !address.is_http() {
+            return Err(AsJsonError::FieldValueInvalid("address"));
+        }
+
         Ok(Server {
             public_key: PublicKey::from_base64(public_key)?,
-            address: address.to_string()
+            address
         })
     }
 }
@@ -74,7 +82,7 @@ pub(crate) mod tests {
     use super::*;
 
     pub fn get_server() -> Server {
-        Server::new(SecretKey::random().public_key(), "localhost:8001")
+        Server::new(SecretKey::random().public_key(), "localhost:8001".parse().unwrap())
     }
 
     #[test]
@@ -83,6 +91,12 @@ pub(crate) mod tests {
 
         assert_eq!(Server::from_json(&server.to_json()?)?, server);
 
+        for address in ["unix:///run/hyperborea.sock", "ws://127.0.0.1:8001", "tcp://127.0.0.1:8001"] {
+            let server = Server::new(server.public_key.clone(), address.parse().unwrap());
+
+            assert!(Server::from_json(&server.to_json()?).is_err());
+        }
+
         Ok(())
     }
 }
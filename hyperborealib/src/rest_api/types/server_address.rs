@@ -0,0 +1,315 @@
+use std::str::FromStr;
+
+/// Scheme used by the addresses without explicitly specified one.
+pub const DEFAULT_SCHEME: &str = "http";
+
+/// Scheme of the Unix domain socket addresses.
+pub const UNIX_SCHEME: &str = "unix";
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ServerAddressError {
+    #[error("Server address has no host: '{0}'")]
+    EmptyHost(String),
+
+    #[error("Server address has invalid port: '{0}'")]
+    InvalidPort(String)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+/// Parsed address of the server.
+/// 
+/// Address has `[scheme://]host[:port][/base/path]` format.
+/// If scheme is not specified, then `http` is used.
+/// 
+/// Unix domain socket addresses have `unix:///path/to/socket`
+/// format. Path to the socket is stored in the `host` field.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use hyperborealib::rest_api::prelude::*;
+/// 
+/// let address = "https://example.org:8443/hyperborea/".parse::<ServerAddress>().unwrap();
+/// 
+/// assert_eq!(address.scheme, "https");
+/// assert_eq!(address.host, "example.org");
+/// assert_eq!(address.port, Some(8443));
+/// assert_eq!(address.base_path, "/hyperborea");
+/// 
+/// assert_eq!(address.endpoint("/api/v1/info"), "https://example.org:8443/hyperborea/api/v1/info");
+/// 
+/// let address = "127.0.0.1:8001".parse::<ServerAddress>().unwrap();
+/// 
+/// assert_eq!(address.endpoint("/api/v1/info"), "http://127.0.0.1:8001/api/v1/info");
+/// ```
+pub struct ServerAddress {
+    /// Lowercased scheme of the address (`http`, `https`, etc.).
+    pub scheme: String,
+
+    /// Domain name or IP address. IPv6 addresses
+    /// are stored in square brackets.
+    pub host: String,
+
+    /// Optional port of the server.
+    pub port: Option<u16>,
+
+    /// Path prefix of the server's REST API. Either
+    /// empty, or starts with `/` and has no trailing `/`.
+    pub base_path: String
+}
+
+impl ServerAddress {
+    /// Create new server address.
+    /// 
+    /// `base_path` is normalized to start with `/`
+    /// and have no trailing `/`.
+    pub fn new(scheme: impl ToString, host: impl ToString, port: Option<u16>, base_path: impl AsRef<str>) -> Self {
+        let base_path = base_path.as_ref().trim_matches('/');
+
+        Self {
+            scheme: scheme.to_string().to_lowercase(),
+            host: host.to_string(),
+            port,
+            base_path: if base_path.is_empty() {
+                String::new()
+            } else {
+                format!("/{base_path}")
+            }
+        }
+    }
+
+    #[inline]
+    /// Check if the address uses `http` or `https` scheme.
+    pub fn is_http(&self) -> bool {
+        self.scheme == "http" || self.scheme == "https"
+    }
+
+    #[inline]
+    /// Check if the address points to a Unix domain socket.
+    pub fn is_unix(&self) -> bool {
+        self.scheme == UNIX_SCHEME
+    }
+
+    /// Build URL of the given REST API endpoint.
+    /// 
+    /// `path` must start with `/`.
+    /// 
+    /// For Unix domain socket addresses the socket path
+    /// is percent-encoded, so it can be separated from the
+    /// endpoint path: `unix://%2Frun%2Fhyperborea.sock/api/v1/info`.
+    /// Use `ServerAddress::split_unix_endpoint` to parse it back.
+    pub fn endpoint(&self, path: impl AsRef<str>) -> String {
+        if self.is_unix() {
+            let socket = self.host
+                .replace('%', "%25")
+                .replace('/', "%2F");
+
+            return format!("{UNIX_SCHEME}://{socket}{}", path.as_ref());
+        }
+
+        format!("{self}{}", path.as_ref())
+    }
+
+    /// Split Unix domain socket endpoint URL built
+    /// by the `endpoint` method into the socket path
+    /// and the HTTP request path.
+    /// 
+    /// Return `None` if the URL doesn't use `unix` scheme.
+    /// 
+    /// # Example
+    /// 
+    /// ```rust
+    /// use hyperborealib::rest_api::prelude::*;
+    /// 
+    /// let address = "unix:///run/hyperborea.sock".parse::<ServerAddress>().unwrap();
+    /// 
+    /// let url = address.endpoint("/api/v1/info");
+    /// 
+    /// assert_eq!(url, "unix://%2Frun%2Fhyperborea.sock/api/v1/info");
+    /// 
+    /// assert_eq!(ServerAddress::split_unix_endpoint(&url), Some((
+    ///     String::from("/run/hyperborea.sock"),
+    ///     String::from("/api/v1/info")
+    /// )));
+    /// ```
+    pub fn split_unix_endpoint(url: &str) -> Option<(String, String)> {
+        let url = url.strip_prefix(UNIX_SCHEME)?.strip_prefix("://")?;
+
+        let (socket, path) = match url.find('/') {
+            Some(i) => url.split_at(i),
+            None => (url, "/")
+        };
+
+        let mut decoded = String::with_capacity(socket.len());
+        let mut chars = socket.chars();
+
+        while let Some(char) = chars.next() {
+            if char != '%' {
+                decoded.push(char);
+
+                continue;
+            }
+
+            let code = chars.by_ref().take(2).collect::<String>();
+
+            match u8::from_str_radix(&code, 16) {
+                Ok(code) => decoded.push(code as char),
+                Err(_) => return None
+            }
+        }
+
+        Some((decoded, path.to_string()))
+    }
+}
+
+impl FromStr for ServerAddress {
+    type Err = ServerAddressError;
+
+    fn from_str(address: &str) -> Result<Self, Self::Err> {
+        let address = address.trim();
+
+        let (scheme, rest) = match address.split_once("://") {
+            Some((scheme, rest)) => (scheme, rest),
+            None => (DEFAULT_SCHEME, address)
+        };
+
+        // Unix domain socket, e.g. unix:///run/hyperborea.sock
+        if scheme.eq_ignore_ascii_case(UNIX_SCHEME) {
+            if rest.is_empty() {
+                return Err(ServerAddressError::EmptyHost(address.to_string()));
+            }
+
+            return Ok(Self::new(scheme, rest, None, ""));
+        }
+
+        let (authority, base_path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, "")
+        };
+
+        // IPv6 address, e.g. [::1]:8001
+        let (host, port) = if authority.starts_with('[') {
+            match authority.find(']') {
+                Some(i) => {
+                    let (host, port) = authority.split_at(i + 1);
+
+                    match port.strip_prefix(':') {
+                        Some(port) => (host, Some(port)),
+                        None if port.is_empty() => (host, None),
+                        None => return Err(ServerAddressError::InvalidPort(address.to_string()))
+                    }
+                }
+
+                None => return Err(ServerAddressError::EmptyHost(address.to_string()))
+            }
+        }
+
+        else {
+            match authority.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None)
+            }
+        };
+
+        if host.is_empty() {
+            return Err(ServerAddressError::EmptyHost(address.to_string()));
+        }
+
+        let port = match port {
+            Some(port) => match port.parse::<u16>() {
+                Ok(port) => Some(port),
+                Err(_) => return Err(ServerAddressError::InvalidPort(address.to_string()))
+            },
+
+            None => None
+        };
+
+        Ok(Self::new(scheme, host, port, base_path))
+    }
+}
+
+impl TryFrom<String> for ServerAddress {
+    type Error = ServerAddressError;
+
+    #[inline]
+    fn try_from(address: String) -> Result<Self, Self::Error> {
+        Self::from_str(&address)
+    }
+}
+
+impl TryFrom<&str> for ServerAddress {
+    type Error = ServerAddressError;
+
+    #[inline]
+    fn try_from(address: &str) -> Result<Self, Self::Error> {
+        Self::from_str(address)
+    }
+}
+
+impl From<ServerAddress> for String {
+    #[inline]
+    fn from(address: ServerAddress) -> Self {
+        address.to_string()
+    }
+}
+
+impl std::fmt::Display for ServerAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}://{}", self.scheme, self.host)?;
+
+        if let Some(port) = self.port {
+            write!(f, ":{port}")?;
+        }
+
+        write!(f, "{}", self.base_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() -> Result<(), ServerAddressError> {
+        let address = ServerAddress::from_str("127.0.0.1:8001")?;
+
+        assert_eq!(address, ServerAddress::new("http", "127.0.0.1", Some(8001), ""));
+        assert_eq!(address.to_string(), "http://127.0.0.1:8001");
+
+        let address = ServerAddress::from_str("HTTPS://example.org/api/prefix/")?;
+
+        assert_eq!(address, ServerAddress::new("https", "example.org", None, "/api/prefix"));
+        assert_eq!(address.to_string(), "https://example.org/api/prefix");
+
+        let address = ServerAddress::from_str("ws://[::1]:9000")?;
+
+        assert_eq!(address, ServerAddress::new("ws", "[::1]", Some(9000), ""));
+        assert!(!address.is_http());
+
+        assert_eq!(ServerAddress::from_str(&address.to_string())?, address);
+
+        let address = ServerAddress::from_str("unix:///run/hyperborea 100%.sock")?;
+
+        assert_eq!(address, ServerAddress::new("unix", "/run/hyperborea 100%.sock", None, ""));
+        assert_eq!(address.to_string(), "unix:///run/hyperborea 100%.sock");
+        assert!(address.is_unix());
+
+        assert_eq!(ServerAddress::from_str(&address.to_string())?, address);
+
+        assert_eq!(
+            ServerAddress::split_unix_endpoint(&address.endpoint("/api/v1/send")),
+            Some((address.host.clone(), String::from("/api/v1/send")))
+        );
+
+        assert_eq!(ServerAddress::split_unix_endpoint("http://127.0.0.1:8001/api/v1/info"), None);
+
+        assert!(ServerAddress::from_str("unix://").is_err());
+        assert!(ServerAddress::from_str("http://:8001").is_err());
+        assert!(ServerAddress::from_str("example.org:port").is_err());
+        assert!(ServerAddress::from_str("[::1]8001").is_err());
+
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
 use hyperborealib::http::client::HttpClient;
 use hyperborealib::rest_api::middleware::Client as ClientMiddleware;
+use hyperborealib::rest_api::types::ServerAddress;
 
-pub async fn command_clients<T: HttpClient>(middleware: &ClientMiddleware<T>, address: impl std::fmt::Display) {
+pub async fn command_clients<T: HttpClient>(middleware: &ClientMiddleware<T>, address: &ServerAddress) {
     match middleware.get_clients(address).await {
         Ok(clients) => {
             log::info!("");
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use hyperborealib::prelude::*;
 
 pub mod args;
@@ -10,6 +12,20 @@ use shell::Shell;
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Parse server address given to the shell command,
+/// logging an error if it's invalid.
+fn parse_address(address: &str) -> Option<ServerAddress> {
+    match ServerAddress::from_str(address) {
+        Ok(address) => Some(address),
+
+        Err(err) => {
+            log::error!("Invalid server address: {err}");
+
+            None
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     env_logger::builder()
@@ -67,7 +83,11 @@ async fn main() -> anyhow::Result<()> {
                             continue;
                         };
 
-                        client::command_info(&middleware, address).await;
+                        let Some(address) = parse_address(address) else {
+                            continue;
+                        };
+
+                        client::command_info(&middleware, &address).await;
                     }
 
                     Some("clients") => {
@@ -77,7 +97,11 @@ async fn main() -> anyhow::Result<()> {
                             continue;
                         };
 
-                        client::command_clients(&middleware, address).await;
+                        let Some(address) = parse_address(address) else {
+                            continue;
+                        };
+
+                        client::command_clients(&middleware, &address).await;
                     }
 
                     Some("servers") => {
@@ -87,7 +111,11 @@ async fn main() -> anyhow::Result<()> {
                             continue;
                         };
 
-                        client::command_servers(&middleware, address).await;
+                        let Some(address) = parse_address(address) else {
+                            continue;
+                        };
+
+                        client::command_servers(&middleware, &address).await;
                     }
 
                     Some("connect") => {
@@ -97,6 +125,10 @@ async fn main() -> anyhow::Result<()> {
                             continue;
                         };
 
+                        let Some(address) = parse_address(address) else {
+                            continue;
+                        };
+
                         match middleware.connect(&address).await {
                             Ok(connected_middlewire) => {
                                 log::info!("");
@@ -119,9 +151,9 @@ async fn main() -> anyhow::Result<()> {
 
                                         Some("exit") => break,
 
-                                        Some("info") => client::command_info(&middleware, address).await,
-                                        Some("clients") => client::command_clients(&middleware, address).await,
-                                        Some("servers") => client::command_servers(&middleware, address).await,
+                                        Some("info") => client::command_info(&middleware, &address).await,
+                                        Some("clients") => client::command_clients(&middleware, &address).await,
+                                        Some("servers") => client::command_servers(&middleware, &address).await,
 
                                         Some("lookup") => {
                                             let Some(public_key) = args.args().first() else {
@@ -172,9 +204,19 @@ async fn main() -> anyhow::Result<()> {
                     Some("exit") => break,
 
                     Some("start") => {
+                        let router = match GlobalTableRouter::new(std::env::temp_dir().join("hyperbox-routing")).await {
+                            Ok(router) => router,
+
+                            Err(err) => {
+                                log::error!("Failed to open routing table: {err}");
+
+                                continue;
+                            }
+                        };
+
                         let server = ServerDriver::new(
-                            GlobalTableRouter::new(),
-                            BfsRecursionTraversal,
+                            router,
+                            BfsRecursionTraversal::default(),
                             StoredQueueMessagesInbox::default(),
                             ServerParams::default()
                         );
@@ -35,7 +35,7 @@ impl ChatHosterApp {
                 .encoding(encoding)
                 .delay(std::time::Duration::from_millis(params.room_sync_delay))
                 .client(client_secret.clone())
-                .server(server_public, &params.client_server_address)
+                .server(server_public, params.client_server_address.parse()?)
                 .build()
                 .unwrap(),
 
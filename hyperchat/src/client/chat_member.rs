@@ -35,14 +35,14 @@ impl ChatMemberApp {
                 .encoding(encoding)
                 .delay(std::time::Duration::from_millis(params.room_sync_delay))
                 .client(client_secret.clone())
-                .server(server_public, &params.client_server_address)
+                .server(server_public, params.client_server_address.parse()?)
                 .build()
                 .unwrap(),
 
             middleware: ClientMiddleware::new(
                 ReqwestHttpClient::default(),
                 ClientDriver::thin(client_secret)
-            ),
+            ).with_lookup_cache(LookupCache::new(std::time::Duration::from_secs(60))),
 
             state: Arc::new(ChatMemberState {
                 members: RwLock::new(HashMap::new()),
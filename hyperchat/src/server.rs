@@ -24,9 +24,16 @@ impl BasicServerApp for ServerApp {
         ServerAppParams {
             secret_key,
             local_address: self.params.server_local_address.clone(),
-            remote_address: self.params.server_exposed_address.clone(),
-            bootstrap: self.params.bootstrap_addresses.clone(),
+            remote_address: self.params.server_exposed_address.parse()
+                .expect("Failed to parse server exposed address"),
+
+            bootstrap: self.params.bootstrap_addresses.iter()
+                .map(|address| address.parse())
+                .collect::<Result<_, _>>()
+                .expect("Failed to parse bootstrap addresses"),
+
             announce: false,
+            announce_clients: false,
             traverse_delay: std::time::Duration::from_secs(self.params.bootstrap_traversal_delay)
         }
     }
@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use serde_json::{json, Value as Json};
 
 use hyperborealib::exports::tokio;
@@ -110,7 +112,7 @@ pub trait ClientApp {
         )?;
 
         middleware.send(
-            endpoint.server_address,
+            &endpoint.server_address,
             endpoint.client_public,
             &params.channel,
             request
@@ -118,10 +120,14 @@ pub trait ClientApp {
 
         // Receive response
         loop {
-            let (messages, _) = middleware.poll(
-                format!("{}@{request_id}", params.channel),
-                Some(1)
-            ).await?;
+            let started_at = Instant::now();
+
+            let channel = format!("{}@{request_id}", params.channel);
+
+            let (messages, _) = match params.wait {
+                Some(wait) => middleware.poll_wait(channel, Some(1), wait).await?,
+                None => middleware.poll(channel, Some(1)).await?
+            };
 
             // If there's an incoming message
             if let Some(message) = messages.first() {
@@ -140,7 +146,7 @@ pub trait ClientApp {
             }
 
             // Sleep otherwise and try again
-            tokio::time::sleep(params.delay).await;
+            tokio::time::sleep(params.delay.saturating_sub(started_at.elapsed())).await;
         }
     }
 
@@ -164,7 +170,7 @@ pub trait ClientApp {
 
         // Send message
         middleware.send(
-            endpoint.server_address,
+            &endpoint.server_address,
             endpoint.client_public,
             &params.channel,
             message
@@ -178,7 +184,10 @@ pub trait ClientApp {
         let params = self.get_params();
         let middleware = self.get_connected_middleware().await?;
 
-        let (messages, _) = middleware.poll(&params.channel, None).await?;
+        let (messages, _) = match params.wait {
+            Some(wait) => middleware.poll_wait(&params.channel, None, wait).await?,
+            None => middleware.poll(&params.channel, None).await?
+        };
 
         for message_info in messages {
             // Decode the message and verify its validity
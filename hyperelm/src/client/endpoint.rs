@@ -1,17 +1,18 @@
 use hyperborealib::crypto::asymmetric::PublicKey;
+use hyperborealib::rest_api::types::ServerAddress;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClientEndpoint {
-    pub server_address: String,
+    pub server_address: ServerAddress,
     pub client_public: PublicKey
 }
 
 impl ClientEndpoint {
     #[inline]
-    pub fn new(server_address: impl ToString, client_public: PublicKey) -> Self {
+    pub fn new(server_address: ServerAddress, client_public: PublicKey) -> Self {
         Self {
-            server_address: server_address.to_string(),
+            server_address,
             client_public
         }
     }
@@ -63,7 +63,7 @@
 ///         todo!()
 ///     }
 ///
-///     fn get_middlewire(&self) ->  &ClientMiddleware<Self::HttpClient>  {
+///     fn get_middleware(&self) ->  &ClientMiddleware<Self::HttpClient>  {
 ///         todo!()
 ///     }
 /// 
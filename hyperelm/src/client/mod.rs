@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Instant;
 
 use hyperborealib::rest_api::middleware::Error;
 
@@ -35,12 +36,15 @@ where
             let params = client.get_params();
 
             loop {
+                let started_at = Instant::now();
+
                 if let Err(_err) = client.update().await {
                     #[cfg(feature = "tracing")]
                     tracing::error!("[client] Update error: {_err}");
                 }
 
-                tokio::time::sleep(params.delay).await;
+                // Long polling requests already waited for messages
+                tokio::time::sleep(params.delay.saturating_sub(started_at.elapsed())).await;
             }
         });
     }
@@ -12,7 +12,7 @@ pub struct ClientAppParams {
     pub server_public: PublicKey,
 
     /// Address of the server to connect to.
-    pub server_address: String,
+    pub server_address: ServerAddress,
 
     /// Messaging channel.
     pub channel: String,
@@ -24,7 +24,14 @@ pub struct ClientAppParams {
     pub compression_level: CompressionLevel,
 
     /// Messages synchronization delay.
-    pub delay: Duration
+    pub delay: Duration,
+
+    /// Long polling timeout.
+    /// 
+    /// If set, then the server holds poll requests until
+    /// messages arrive or this timeout expires. Poll requests
+    /// are still sent not more often than once per `delay`.
+    pub wait: Option<Duration>
 }
 
 impl ClientAppParams {
@@ -43,7 +50,7 @@ pub struct ClientAppParamsBuilder {
     pub server_public: Option<PublicKey>,
 
     /// Address of the server to connect to.
-    pub server_address: Option<String>,
+    pub server_address: Option<ServerAddress>,
 
     /// Messaging channel.
     pub channel: String,
@@ -55,7 +62,10 @@ pub struct ClientAppParamsBuilder {
     pub compression_level: CompressionLevel,
 
     /// Messages synchronization delay.
-    pub delay: Duration
+    pub delay: Duration,
+
+    /// Long polling timeout.
+    pub wait: Option<Duration>
 }
 
 impl Default for ClientAppParamsBuilder {
@@ -67,7 +77,8 @@ impl Default for ClientAppParamsBuilder {
             channel: String::from("hyperelm"),
             encoding: MessageEncoding::default(),
             compression_level: CompressionLevel::default(),
-            delay: Duration::from_secs(1)
+            delay: Duration::from_secs(1),
+            wait: None
         }
     }
 }
@@ -79,9 +90,9 @@ impl ClientAppParamsBuilder {
         self
     }
 
-    pub fn server(mut self, public_key: PublicKey, address: impl ToString) -> Self {
+    pub fn server(mut self, public_key: PublicKey, address: ServerAddress) -> Self {
         self.server_public = Some(public_key);
-        self.server_address = Some(address.to_string());
+        self.server_address = Some(address);
 
         self
     }
@@ -110,6 +121,12 @@ impl ClientAppParamsBuilder {
         self
     }
 
+    pub fn wait(mut self, wait: Duration) -> Self {
+        self.wait = Some(wait);
+
+        self
+    }
+
     pub fn build(self) -> Option<ClientAppParams> {
         Some(ClientAppParams {
             client_secret: self.client_secret?,
@@ -118,7 +135,8 @@ impl ClientAppParamsBuilder {
             channel: self.channel,
             encoding: self.encoding,
             compression_level: self.compression_level,
-            delay: self.delay
+            delay: self.delay,
+            wait: self.wait
         })
     }
 }
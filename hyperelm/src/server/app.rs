@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use hyperborealib::http::*;
 use hyperborealib::rest_api::prelude::*;
 use hyperborealib::drivers::prelude::*;
@@ -38,7 +40,17 @@ pub trait ServerApp {
             self.get_messages_inbox()?,
             ServerParams {
                 secret_key: params.secret_key.clone(),
-                address: params.remote_address.clone()
+                address: params.remote_address.clone(),
+                request_lifetime: Duration::from_secs(60 * 5),
+                replay_guard_capacity: 256 * 1024,
+                min_standard: 2,
+                forward_messages: false,
+                probe_announced_servers: false,
+                max_poll_wait: Duration::from_secs(30),
+                max_lease: Duration::from_secs(60 * 10),
+                max_batch_size: 128,
+                lookup_max_hops: 0,
+                lookup_timeout: Duration::from_secs(10)
             }
         ))
     }
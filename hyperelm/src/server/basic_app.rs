@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use hyperborealib::drivers::prelude::*;
 use hyperborealib::http::*;
 
@@ -9,7 +11,7 @@ use super::*;
 /// ```rust
 /// use hyperelm::prelude::*;
 /// 
-/// use hyperborealib::crypto::SecretKey;
+/// use hyperborealib::crypto::asymmetric::SecretKey;
 /// 
 /// struct MyServerApp;
 /// 
@@ -18,9 +20,10 @@ use super::*;
 ///         ServerAppParams {
 ///             secret_key: SecretKey::random(),
 ///             local_address: String::from("127.0.0.1:8001"),
-///             remote_address: String::from("127.0.0.1:8001"),
+///             remote_address: "127.0.0.1:8001".parse().unwrap(),
 ///             bootstrap: vec![],
 ///             announce: false,
+///             announce_clients: false,
 ///             traverse_delay: std::time::Duration::from_secs(60 * 10)
 ///         }
 ///     }
@@ -28,31 +31,47 @@ use super::*;
 /// ```
 pub trait BasicServerApp {
     fn get_params(&self) -> ServerAppParams;
+
+    #[inline]
+    /// Get path to the routing table's folder.
+    /// 
+    /// By default it's the `routing` folder
+    /// in the current working directory.
+    fn get_routing_folder(&self) -> PathBuf {
+        PathBuf::from("routing")
+    }
 }
 
 impl<T> ServerApp for T where T: BasicServerApp {
     type Router = GlobalTableRouter;
     type Traversal = BfsRecursionTraversal;
-    type MessagesInbox = BasicInbox;
+    type MessagesInbox = StoredQueueMessagesInbox;
 
     type HttpClient = ReqwestHttpClient;
     type HttpServer = AxumHttpServer;
 
     type Error = ();
 
-    #[inline]
     fn get_router(&self) -> Result<Self::Router, Self::Error>  {
-        Ok(GlobalTableRouter::default())
+        // Router's folders are created asynchronously, so the current
+        // worker thread is temporarily turned into a blocking one
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current()
+                .block_on(GlobalTableRouter::new(self.get_routing_folder()))
+        }).map_err(|_err| {
+            #[cfg(feature = "tracing")]
+            tracing::error!("[server] Failed to open routing table: {_err}");
+        })
     }
 
     #[inline]
     fn get_traversal(&self) -> Result<Self::Traversal, Self::Error>  {
-        Ok(BfsRecursionTraversal)
+        Ok(BfsRecursionTraversal::default())
     }
 
     #[inline]
     fn get_messages_inbox(&self) -> Result<Self::MessagesInbox, Self::Error>  {
-        Ok(BasicInbox::default())
+        Ok(StoredQueueMessagesInbox::default())
     }
 
     #[inline]
@@ -1,3 +1,10 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use futures::{StreamExt, TryStreamExt};
+
+use hyperborealib::crypto::asymmetric::PublicKey;
+use hyperborealib::http::HttpClient;
 use hyperborealib::drivers::prelude::*;
 use hyperborealib::rest_api::prelude::*;
 
@@ -28,32 +35,46 @@ where
     // Create client middlewire for traversal thread
     let traversal_client = ClientMiddleware::new(
         app.get_http_client()?,
-        ClientDriver::new(ClientInfo::thin(), params.secret_key)
+        ClientDriver::new(ClientInfo::thin(), params.secret_key.clone())
     );
 
     // Resolve server middlewire and driver
-    let middlewire = app.get_middlewire().await.unwrap();
+    let middlewire = app.get_middlewire().await?;
     let driver = middlewire.driver();
 
     // Start the server
+    let local_address = params.local_address.clone();
+
     tokio::spawn(async move {
-        if let Err(_err) = middlewire.serve(&params.local_address).await {
+        if let Err(_err) = middlewire.serve(&local_address).await {
             #[cfg(feature = "tracing")]
             tracing::error!("[server] {_err}");
         }
     });
 
+    let mut announce_backoffs = HashMap::new();
+
     loop {
         // Index bootstrap servers
         #[cfg(feature = "tracing")]
         tracing::debug!("[server] Indexing bootstrap addresses");
 
         for address in &params.bootstrap {
-            if let Ok(server) = traversal_client.get_info(&address).await {
-                driver.router().index_server(Server::new(
+            // Only http(s) servers can be shared with the network
+            if !address.is_http() {
+                continue;
+            }
+
+            if let Ok(server) = traversal_client.get_info(address).await {
+                let result = driver.router().index_server(Server::new(
                     server.public_key,
-                    address
+                    address.clone()
                 )).await;
+
+                if let Err(_err) = result {
+                    #[cfg(feature = "tracing")]
+                    tracing::error!("[server] Failed to index bootstrap server {address}: {_err}");
+                }
             }
         }
 
@@ -68,10 +89,167 @@ where
 
         // Announce servers about ourselves
         if params.announce {
-            // TODO
+            #[cfg(feature = "tracing")]
+            tracing::debug!("[server] Announcing remote servers");
+
+            announce(&traversal_client, &driver, &params, &mut announce_backoffs).await;
         }
 
         // Wait before repeating
         tokio::time::sleep(params.traverse_delay).await;
     }
 }
+
+/// Maximal amount of skipped announcement cycles
+/// for a remote server which failed to be announced.
+const MAX_ANNOUNCE_BACKOFF: u32 = 64;
+
+/// Amount of remote servers announced at the same time.
+const ANNOUNCE_CONCURRENCY: usize = 16;
+
+/// Amount of local clients announced to
+/// a single remote server at the same time.
+const ANNOUNCE_CLIENTS_CONCURRENCY: usize = 8;
+
+/// Maximal time given to a single announcement request.
+const ANNOUNCE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Default, Clone, Copy)]
+/// Backoff of a remote server which
+/// failed to receive our announcements.
+struct AnnounceBackoff {
+    /// Amount of failed announcements in a row.
+    failures: u32,
+
+    /// Amount of announcement cycles to skip.
+    skip: u32
+}
+
+/// Announce all the known servers about the current
+/// server and (optionally) its local clients.
+/// 
+/// Servers which failed to receive the announcement
+/// are skipped for exponentially growing amount
+/// of cycles.
+async fn announce<T, R, Tr, I>(
+    client: &ClientMiddleware<T>,
+    driver: &ServerDriver<R, Tr, I>,
+    params: &ServerAppParams,
+    backoffs: &mut HashMap<PublicKey, AnnounceBackoff>
+)
+where
+    T: HttpClient,
+    R: Router + Sync,
+    Tr: Traversal + Sync,
+    I: MessagesInbox + Sync
+{
+    let current_server = Server::new(
+        params.secret_key.public_key(),
+        params.remote_address.clone()
+    );
+
+    let servers = match driver.router().servers().await {
+        Ok(servers) => servers,
+
+        Err(_err) => {
+            #[cfg(feature = "tracing")]
+            tracing::error!("[server] Failed to read known servers: {_err}");
+
+            return;
+        }
+    };
+
+    let clients = if params.announce_clients {
+        driver.router().local_clients().await.unwrap_or_else(|_err| {
+            #[cfg(feature = "tracing")]
+            tracing::error!("[server] Failed to read local clients: {_err}");
+
+            vec![]
+        })
+    } else {
+        vec![]
+    };
+
+    // Forget backoffs of the servers removed from the router
+    backoffs.retain(|public_key, _| {
+        servers.iter().any(|server| &server.public_key == public_key)
+    });
+
+    // Skip current server and recently failed servers
+    let servers = servers.into_iter()
+        .filter(|server| server.public_key != current_server.public_key)
+        .filter(|server| {
+            match backoffs.get_mut(&server.public_key) {
+                Some(backoff) if backoff.skip > 0 => {
+                    backoff.skip -= 1;
+
+                    false
+                }
+
+                _ => true
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let results = futures::stream::iter(servers)
+        .map(|server| {
+            let current_server = &current_server;
+            let clients = &clients;
+
+            async move {
+                let result = async {
+                    announce_with_timeout(
+                        client,
+                        &server.address,
+                        AnnounceRequestBody::server(current_server.clone())
+                    ).await?;
+
+                    futures::stream::iter(clients)
+                        .map(Ok)
+                        .try_for_each_concurrent(ANNOUNCE_CLIENTS_CONCURRENCY, |local_client| {
+                            announce_with_timeout(
+                                client,
+                                &server.address,
+                                AnnounceRequestBody::client(local_client.clone(), current_server.clone())
+                            )
+                        })
+                        .await
+                }.await;
+
+                (server, result)
+            }
+        })
+        .buffer_unordered(ANNOUNCE_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+
+    for (server, result) in results {
+        match result {
+            Ok(()) => {
+                backoffs.remove(&server.public_key);
+            }
+
+            Err(_err) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("[server] Failed to announce server {}: {_err}", server.address);
+
+                let backoff = backoffs.entry(server.public_key)
+                    .or_default();
+
+                backoff.failures += 1;
+                backoff.skip = 2u32.saturating_pow(backoff.failures).min(MAX_ANNOUNCE_BACKOFF) - 1;
+            }
+        }
+    }
+}
+
+/// Send announcement to the remote server
+/// within the `ANNOUNCE_TIMEOUT`.
+async fn announce_with_timeout<T: HttpClient>(
+    client: &ClientMiddleware<T>,
+    server_address: &ServerAddress,
+    announcement: AnnounceRequestBody
+) -> Result<(), MiddlewareError> {
+    tokio::time::timeout(ANNOUNCE_TIMEOUT, client.announce(server_address, announcement)).await
+        .map_err(|_| MiddlewareError::Other("Announcement timed out".into()))?
+}
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use hyperborealib::crypto::asymmetric::SecretKey;
+use hyperborealib::rest_api::types::ServerAddress;
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -14,13 +15,13 @@ pub struct ServerAppParams {
 
     /// Address by which other clients can access
     /// current server through the Internet.
-    pub remote_address: String,
+    pub remote_address: ServerAddress,
 
     /// Bootstrap addresses used to gather
     /// initial information about the network.
     /// 
     /// Usually some static server addresses.
-    pub bootstrap: Vec<String>,
+    pub bootstrap: Vec<ServerAddress>,
 
     /// Announce current server to other servers.
     /// 
@@ -31,6 +32,12 @@ pub struct ServerAppParams {
     /// your server can't be accessed through the internet.
     pub announce: bool,
 
+    /// Announce local clients of the current server
+    /// to other servers.
+    /// 
+    /// This option is used only if `announce` is enabled.
+    pub announce_clients: bool,
+
     /// Network traversing delay.
     /// 
     /// Traversing is performed to gather information